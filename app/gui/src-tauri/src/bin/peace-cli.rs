@@ -0,0 +1,284 @@
+//! Headless control of the node infrastructure, for operators running Peace
+//! Protocol on a server with no WebView to click through.
+//!
+//! This intentionally does *not* introduce a second, Tauri-free backend: the
+//! command layer already separates "things that touch `tauri::State`" from
+//! "things that don't" along a useful line — `process::mithril`,
+//! `process::cardano`, etc. take plain `&NodeManager`/`&AppConfig` and are
+//! already backend-agnostic, while `commands::wallet`'s `#[tauri::command]`
+//! functions only need a `tauri::State<T>`, which `AppHandle::state::<T>()`
+//! hands back identically whether or not a window exists. So this binary
+//! builds the same headless `tauri::App` the GUI builds (via
+//! `peace_protocol_lib::managed_state`, no window ever created) and calls the
+//! exact same functions the GUI's `invoke_handler` calls, rather than
+//! duplicating their logic behind a parallel trait hierarchy.
+use clap::{Parser, Subcommand};
+use peace_protocol_lib::commands::{node, wallet, wizard};
+use peace_protocol_lib::crypto::secrets::SecretsKey;
+use peace_protocol_lib::process::instance_lock::InstanceLock;
+use peace_protocol_lib::process::manager::NodeManager;
+use peace_protocol_lib::process::mithril;
+use peace_protocol_lib::process::ogmios_client::OgmiosClient;
+use peace_protocol_lib::{commands::wallet::WalletState, config::AppConfig};
+use tauri::Manager;
+
+#[derive(Parser)]
+#[command(name = "peace-cli", about = "Headless control of Peace Protocol node infrastructure")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Wallet unlock/lock, without a GUI prompt.
+    Wallet {
+        #[command(subcommand)]
+        action: WalletAction,
+    },
+    /// Start the node stack, or trigger a Mithril bootstrap.
+    Node {
+        #[command(subcommand)]
+        action: NodeAction,
+    },
+    /// Print the current aggregate node status as JSON.
+    Status,
+    /// First-run config generation, for an operator with no config.json yet.
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Interactively prompt for network/ports on stdin and write config.json
+    /// — the headless counterpart to the GUI's first-run wizard, via the
+    /// same `wizard::run_config_wizard_headless`.
+    Init,
+}
+
+#[derive(Subcommand)]
+enum WalletAction {
+    /// Decrypt the wallet and derive the secrets encryption key.
+    ///
+    /// Takes no `--password` flag on purpose: this binary's whole premise is
+    /// an operator unlocking a wallet on a server, and a plaintext password
+    /// flag would land in shell history and be visible to any other user on
+    /// the box via `ps`. See `read_unlock_password` for where it comes from
+    /// instead.
+    Unlock,
+    /// Clear the decrypted mnemonic and secrets key from memory.
+    Lock,
+}
+
+/// Name of the env var `Unlock` falls back to when it's not run from a
+/// terminal (e.g. under a process supervisor, where there's no stdin to
+/// prompt on) — still not visible via `ps` the way a CLI flag would be,
+/// unlike the environment of *other* processes, which this can't protect
+/// against, so prefer the interactive prompt wherever one's available.
+const WALLET_PASSWORD_ENV_VAR: &str = "PEACE_WALLET_PASSWORD";
+
+/// Get the wallet password from the environment if set, otherwise prompt
+/// for it on stdin with echo disabled — never accepted as a CLI argument.
+fn read_unlock_password() -> Result<String, String> {
+    if let Ok(password) = std::env::var(WALLET_PASSWORD_ENV_VAR) {
+        return Ok(password);
+    }
+    rpassword::prompt_password("Wallet password: ")
+        .map_err(|e| format!("Failed to read password: {e}"))
+}
+
+#[derive(Subcommand)]
+enum NodeAction {
+    /// Start cardano-node, Ogmios, Kupo, and Express, in dependency order.
+    Start,
+    /// Run a full Mithril bootstrap and block until it's verified and extracted.
+    Bootstrap,
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let app = tauri::Builder::default()
+        .plugin(tauri_plugin_shell::init())
+        .build(tauri::generate_context!())
+        .expect("error while building headless application");
+
+    let app_handle = app.handle().clone();
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .expect("Failed to resolve app data directory");
+
+    // Same single-instance guard the GUI takes in `run()` — a CLI bootstrap
+    // or node start racing a running GUI on the same wallet/node-db would be
+    // exactly as unsafe as two GUIs racing each other.
+    match InstanceLock::try_acquire(&app_data_dir) {
+        Ok(Some(lock)) => {
+            app_handle.manage(lock);
+        }
+        Ok(None) => {
+            eprintln!("Peace Protocol is already running. Only one instance can run at a time.");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("[instance-lock] {e}");
+        }
+    }
+
+    if let Err(e) = peace_protocol_lib::managed_state(&app_handle, &app_data_dir) {
+        eprintln!("Failed to initialize application state: {e}");
+        std::process::exit(1);
+    }
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to start async runtime");
+
+    let result = runtime.block_on(run_command(cli.command, app_handle));
+
+    if let Err(e) = result {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run_command(command: Command, app_handle: tauri::AppHandle) -> Result<(), String> {
+    match command {
+        Command::Wallet { action } => run_wallet_action(action, &app_handle),
+        Command::Node { action } => run_node_action(action, &app_handle).await,
+        Command::Status => run_status(&app_handle).await,
+        Command::Config { action } => run_config_action(action, &app_handle),
+    }
+}
+
+fn run_config_action(action: ConfigAction, app_handle: &tauri::AppHandle) -> Result<(), String> {
+    match action {
+        ConfigAction::Init => {
+            let app_data_dir = app_handle
+                .path()
+                .app_data_dir()
+                .map_err(|e| format!("Failed to resolve app data dir: {e}"))?;
+
+            // Same dev-resource-file-vs-app-data-dir resolution
+            // `run_config_wizard`/`set_network` use, so a CLI-generated
+            // config lands exactly where the GUI would look for it.
+            let dev_path =
+                std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources/config.json");
+            let config_path = if dev_path.exists() {
+                dev_path
+            } else {
+                app_data_dir.join("config.json")
+            };
+
+            wizard::run_config_wizard_headless(&config_path, &app_data_dir)?;
+            println!("Config written to {}", config_path.display());
+            Ok(())
+        }
+    }
+}
+
+fn run_wallet_action(action: WalletAction, app_handle: &tauri::AppHandle) -> Result<(), String> {
+    match action {
+        WalletAction::Unlock => {
+            let password = read_unlock_password()?;
+            let wallet_state = app_handle.state::<WalletState>();
+            let secrets_key_state = app_handle.state::<SecretsKey>();
+            let words = wallet::unlock_wallet(wallet_state, secrets_key_state, password)?;
+            println!("Wallet unlocked ({} word mnemonic recovered).", words.len());
+            Ok(())
+        }
+        WalletAction::Lock => {
+            let wallet_state = app_handle.state::<WalletState>();
+            let secrets_key_state = app_handle.state::<SecretsKey>();
+            wallet::lock_wallet(wallet_state, secrets_key_state)?;
+            println!("Wallet locked.");
+            Ok(())
+        }
+    }
+}
+
+async fn run_node_action(action: NodeAction, app_handle: &tauri::AppHandle) -> Result<(), String> {
+    match action {
+        NodeAction::Start => run_node_start(app_handle).await,
+        NodeAction::Bootstrap => run_node_bootstrap(app_handle).await,
+    }
+}
+
+/// Start the full node stack in the foreground and block until Ctrl-C, at
+/// which point it runs the exact same `kill_all_sync` shutdown the GUI's
+/// window-close handler uses, so a CLI-started stack shuts down exactly as
+/// cleanly as a GUI-started one.
+async fn run_node_start(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let manager = app_handle.state::<NodeManager>();
+    let ogmios_client = app_handle.state::<OgmiosClient>();
+    node::start_node(manager, ogmios_client, app_handle.clone()).await?;
+
+    println!("Node infrastructure started. Press Ctrl-C to stop.");
+
+    tokio::signal::ctrl_c()
+        .await
+        .map_err(|e| format!("Failed to listen for Ctrl-C: {e}"))?;
+
+    println!("Shutting down...");
+    app_handle.state::<NodeManager>().kill_all_sync();
+    Ok(())
+}
+
+/// Run a full Mithril bootstrap, printing each observed `MithrilProgress`
+/// update as a line-buffered JSON line on stdout so an operator (or a
+/// supervising process reading this CLI's stdout) can track download/verify/
+/// extract progress live instead of waiting for one final result line.
+async fn run_node_bootstrap(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let manager = app_handle.state::<NodeManager>();
+    let config = app_handle.state::<AppConfig>();
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {e}"))?;
+
+    if !mithril::needs_bootstrap(&config, &app_data_dir) {
+        println!("Chain data already present; nothing to bootstrap.");
+        return Ok(());
+    }
+
+    let node_db_dir = config.node_db_dir(&app_data_dir);
+    let monitor = tokio::spawn(stream_progress(node_db_dir));
+
+    let result = mithril::bootstrap_and_verify(&manager, &config, &app_data_dir).await;
+    monitor.abort();
+    result
+}
+
+async fn stream_progress(node_db_dir: std::path::PathBuf) {
+    use std::io::Write;
+
+    let mut last_line = String::new();
+    loop {
+        if let Some(progress) = mithril::read_progress(&node_db_dir) {
+            if let Ok(line) = serde_json::to_string(&progress) {
+                if line != last_line {
+                    println!("{line}");
+                    let _ = std::io::stdout().flush();
+                    last_line = line;
+                }
+                if progress.stage == mithril::MithrilStage::Complete {
+                    break;
+                }
+            }
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+}
+
+async fn run_status(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let manager = app_handle.state::<NodeManager>();
+    let ogmios_client = app_handle.state::<OgmiosClient>();
+    let status = node::get_node_status(manager, ogmios_client, app_handle.clone()).await?;
+    let json = serde_json::to_string_pretty(&status)
+        .map_err(|e| format!("Failed to serialize status: {e}"))?;
+    println!("{json}");
+    Ok(())
+}
@@ -0,0 +1,386 @@
+use crate::crypto::secrets::{
+    decrypt_secret, encrypt_secret_v2, EncryptedSecret, NonceSequence, SecretsKey,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use super::secrets::SecretsDir;
+
+/// Domain separator for hashing a sync key into a filename/URL-path
+/// component, same role as `crypto::kv_store::FILENAME_SALT`: `key` is a
+/// free-form string supplied by the frontend over IPC, so it's hashed
+/// rather than used as-is — a value like `../../../whatever` must never
+/// reach `std::fs::write`/a request URL as a literal path segment.
+const SYNC_KEY_SALT: &[u8] = b"PEACE_BACKUP_KEY_V1";
+
+fn hashed_key(key: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(SYNC_KEY_SALT);
+    hasher.update(key.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Managed state holding the reqwest client used by `HttpSecretsStore`,
+/// built once at startup like `IagonHttp` rather than per-call.
+pub struct BackupHttp(pub reqwest::Client);
+
+impl BackupHttp {
+    pub fn new() -> Self {
+        Self(reqwest::Client::new())
+    }
+}
+
+impl Default for BackupHttp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn backup_dir(base: &Path) -> PathBuf {
+    base.join("backup")
+}
+
+/// Write `data` to `path` atomically: write to a sibling temp file, then
+/// rename over the real path. A crash or power loss mid-write leaves the
+/// old file (or nothing, on first write) rather than a truncated one that
+/// `load_server_config`/`LocalSecretsStore::pull` would fail to parse.
+fn atomic_write(path: &Path, data: &[u8]) -> Result<(), String> {
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, data)
+        .map_err(|e| format!("Failed to write {}: {e}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| format!("Failed to finalize {}: {e}", path.display()))
+}
+
+/// Reject anything but a well-formed `http(s)://` URL before it's ever
+/// persisted — `backup_push_secret`/`backup_pull_secret` dispatch real
+/// outbound requests to whatever's saved here, so an unvalidated
+/// frontend-supplied `server_url` (e.g. a bare host, or a non-HTTP scheme)
+/// would let that traffic be pointed anywhere, same risk `download_image`
+/// guards against for image URLs.
+fn validate_server_url(url: &str) -> Result<(), String> {
+    let parsed: reqwest::Url = url.parse().map_err(|_| "Invalid backup server URL".to_string())?;
+    let scheme = parsed.scheme();
+    if scheme != "http" && scheme != "https" {
+        return Err("Backup server URL must use http:// or https://".to_string());
+    }
+    Ok(())
+}
+
+/// Filename (relative to a `SecretsDir` base) of the nonce counter every
+/// v2-encrypting call site in this vault shares — same counter
+/// `commands::secrets` rotates through, so no two secrets under the same
+/// key ever draw the same nonce no matter which command wrote them.
+const NONCE_COUNTER_FILENAME: &str = "nonce_counter";
+
+const SERVER_CONFIG_FILENAME: &str = "server.json";
+
+/// User-supplied backup server URL, persisted alongside the other secrets
+/// config files. `None` means backup sync is disabled — `backup_push_secret`/
+/// `backup_pull_secret` only touch the local versioned store in that case.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct BackupServerConfig {
+    #[serde(default)]
+    pub server_url: Option<String>,
+}
+
+#[tauri::command]
+pub fn get_backup_server_config(
+    state: tauri::State<'_, SecretsDir>,
+) -> Result<BackupServerConfig, String> {
+    load_server_config(&state.0)
+}
+
+#[tauri::command]
+pub fn set_backup_server_config(
+    state: tauri::State<'_, SecretsDir>,
+    config: BackupServerConfig,
+) -> Result<(), String> {
+    if let Some(server_url) = &config.server_url {
+        validate_server_url(server_url)?;
+    }
+
+    let dir = backup_dir(&state.0);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create backup dir: {e}"))?;
+    let json = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize backup server config: {e}"))?;
+    atomic_write(&dir.join(SERVER_CONFIG_FILENAME), json.as_bytes())
+}
+
+fn load_server_config(base: &Path) -> Result<BackupServerConfig, String> {
+    let path = backup_dir(base).join(SERVER_CONFIG_FILENAME);
+    if !path.exists() {
+        return Ok(BackupServerConfig::default());
+    }
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read backup server config: {e}"))?;
+    serde_json::from_str(&json).map_err(|e| format!("Invalid backup server config: {e}"))
+}
+
+/// A secret blob plus the per-key version counter `SecretsStore` tracks it
+/// under. `encrypted` carries the same `nonce`/`ciphertext` an
+/// `EncryptedSecret` always has — the store never sees anything else, so a
+/// remote `SecretsStore` impl is zero-knowledge by construction rather than
+/// by convention we have to remember to uphold at every call site.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SyncedSecret {
+    pub version: u64,
+    pub encrypted: EncryptedSecret,
+}
+
+/// Where `SyncedSecret` blobs are pushed to and pulled from. One impl talks
+/// to the local filesystem (used on its own when no server is configured,
+/// and as the "local side" `backup_pull_secret` compares against); the other talks
+/// to a user-supplied HTTP backup server, modeled on Mutiny's versioned
+/// storage client.
+#[async_trait]
+pub trait SecretsStore: Send + Sync {
+    async fn push(&self, key: &str, record: &SyncedSecret) -> Result<(), String>;
+    async fn pull(&self, key: &str) -> Result<Option<SyncedSecret>, String>;
+}
+
+/// Local-filesystem `SecretsStore`, one JSON file per key under
+/// `<base>/backup/local/<hashed_key(key)>.json`. This is the "local side" of every
+/// sync: what `push_secret` compares the remote against, and the fallback
+/// store when no backup server is configured.
+pub struct LocalSecretsStore {
+    dir: PathBuf,
+}
+
+impl LocalSecretsStore {
+    pub fn new(base: &Path) -> Self {
+        Self {
+            dir: backup_dir(base).join("local"),
+        }
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", hashed_key(key)))
+    }
+}
+
+#[async_trait]
+impl SecretsStore for LocalSecretsStore {
+    async fn push(&self, key: &str, record: &SyncedSecret) -> Result<(), String> {
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create local backup store dir: {e}"))?;
+        let json = serde_json::to_string_pretty(record)
+            .map_err(|e| format!("Failed to serialize synced secret: {e}"))?;
+        atomic_write(&self.path_for(key), json.as_bytes())
+    }
+
+    async fn pull(&self, key: &str) -> Result<Option<SyncedSecret>, String> {
+        let path = self.path_for(key);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read local backup entry: {e}"))?;
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| format!("Invalid local backup entry: {e}"))
+    }
+}
+
+/// HTTP `SecretsStore` backed by a user-supplied server. `push` is a PUT of
+/// `{version, encrypted}` to `{base_url}/secrets/{hashed_key(key)}`; `pull` is a GET of
+/// the same URL, where a 404 means "no object stored for this key yet"
+/// rather than an error. The server only ever receives hex `nonce`/
+/// `ciphertext` and a version integer — never plaintext or the
+/// mnemonic-derived key, since `encrypted` is already sealed by the caller
+/// before it reaches this impl.
+pub struct HttpSecretsStore<'a> {
+    client: &'a reqwest::Client,
+    base_url: String,
+}
+
+impl<'a> HttpSecretsStore<'a> {
+    pub fn new(client: &'a reqwest::Client, base_url: String) -> Self {
+        Self { client, base_url }
+    }
+
+    fn url_for(&self, key: &str) -> String {
+        format!(
+            "{}/secrets/{}",
+            self.base_url.trim_end_matches('/'),
+            hashed_key(key)
+        )
+    }
+}
+
+#[async_trait]
+impl<'a> SecretsStore for HttpSecretsStore<'a> {
+    async fn push(&self, key: &str, record: &SyncedSecret) -> Result<(), String> {
+        let resp = self
+            .client
+            .put(self.url_for(key))
+            .json(record)
+            .send()
+            .await
+            .map_err(|e| format!("Backup server request failed: {e}"))?;
+
+        if !resp.status().is_success() {
+            return Err(format!(
+                "Backup server rejected push (status {})",
+                resp.status()
+            ));
+        }
+        Ok(())
+    }
+
+    async fn pull(&self, key: &str) -> Result<Option<SyncedSecret>, String> {
+        let resp = self
+            .client
+            .get(self.url_for(key))
+            .send()
+            .await
+            .map_err(|e| format!("Backup server request failed: {e}"))?;
+
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            return Err(format!(
+                "Backup server rejected pull (status {})",
+                resp.status()
+            ));
+        }
+
+        resp.json::<SyncedSecret>()
+            .await
+            .map(Some)
+            .map_err(|e| format!("Invalid response from backup server: {e}"))
+    }
+}
+
+/// Outcome of `push_secret`: either the local record was accepted, or the
+/// remote already held a strictly newer version — the push is refused
+/// rather than overwriting it, and the caller gets the conflicting remote
+/// record back to adopt instead.
+pub enum SyncOutcome {
+    Pushed,
+    Conflict { remote: SyncedSecret },
+}
+
+/// Push `local` to `store`, refusing to clobber a remote version that's
+/// already ahead of it. This is the monotonic half of the conflict
+/// resolution: a remote object's version only ever goes up, so a push
+/// carrying a version at or behind what's already there either restates
+/// what the server has (no-op) or — if the server's version actually
+/// exceeds the one being pushed — is rejected as a conflict rather than
+/// silently overwritten.
+pub async fn push_secret(
+    store: &dyn SecretsStore,
+    key: &str,
+    local: &SyncedSecret,
+) -> Result<SyncOutcome, String> {
+    if let Some(remote) = store.pull(key).await? {
+        if remote.version > local.version {
+            return Ok(SyncOutcome::Conflict { remote });
+        }
+    }
+    store.push(key, local).await?;
+    Ok(SyncOutcome::Pushed)
+}
+
+/// Encrypt `plaintext` under the unlocked secrets key and push it to the
+/// local versioned store (version bumped by one past whatever's already
+/// there) and, if a backup server is configured, to it as well. Returns
+/// the version the object was stored at, or a conflict if the remote
+/// already has a newer version than the local store did.
+#[tauri::command]
+pub async fn backup_push_secret(
+    state: tauri::State<'_, SecretsDir>,
+    key_state: tauri::State<'_, SecretsKey>,
+    http: tauri::State<'_, BackupHttp>,
+    key: String,
+    plaintext: Vec<u8>,
+) -> Result<u64, String> {
+    let secrets_key = key_state.get()?;
+    let local_store = LocalSecretsStore::new(&state.0);
+
+    let next_version = match local_store.pull(&key).await? {
+        Some(existing) => existing.version + 1,
+        None => 1,
+    };
+    let nonce_seq = NonceSequence::new(state.0.join(NONCE_COUNTER_FILENAME));
+    let record = SyncedSecret {
+        version: next_version,
+        encrypted: encrypt_secret_v2(&secrets_key, &plaintext, &nonce_seq)?,
+    };
+
+    match push_secret(&local_store, &key, &record).await? {
+        SyncOutcome::Pushed => {}
+        SyncOutcome::Conflict { remote } => {
+            return Err(format!(
+                "Local backup store already has a newer version ({}) than {}",
+                remote.version, next_version
+            ))
+        }
+    }
+
+    let server_config = load_server_config(&state.0)?;
+    if let Some(server_url) = server_config.server_url {
+        let remote_store = HttpSecretsStore::new(&http.0, server_url);
+        match push_secret(&remote_store, &key, &record).await? {
+            SyncOutcome::Pushed => {}
+            SyncOutcome::Conflict { remote } => {
+                return Err(format!(
+                    "Backup server already has a newer version ({}) for \"{key}\" — pull and merge before pushing",
+                    remote.version
+                ))
+            }
+        }
+    }
+
+    Ok(record.version)
+}
+
+/// Pull the secret stored for `key`, preferring whichever side — local
+/// store or (if configured) backup server — has the higher version, and
+/// adopting it into the local store so a subsequent push compares against
+/// the right baseline. Returns the decrypted plaintext, or `None` if
+/// neither side has anything stored for `key`.
+#[tauri::command]
+pub async fn backup_pull_secret(
+    state: tauri::State<'_, SecretsDir>,
+    key_state: tauri::State<'_, SecretsKey>,
+    http: tauri::State<'_, BackupHttp>,
+    key: String,
+) -> Result<Option<Vec<u8>>, String> {
+    let secrets_key = key_state.get()?;
+    let local_store = LocalSecretsStore::new(&state.0);
+    let local = local_store.pull(&key).await?;
+
+    let server_config = load_server_config(&state.0)?;
+    let winner = match server_config.server_url {
+        Some(server_url) => {
+            let remote_store = HttpSecretsStore::new(&http.0, server_url);
+            let remote = remote_store.pull(&key).await?;
+            match (local, remote) {
+                (Some(l), Some(r)) if r.version > l.version => Some(r),
+                (Some(l), Some(_)) => Some(l),
+                (Some(l), None) => Some(l),
+                (None, Some(r)) => Some(r),
+                (None, None) => None,
+            }
+        }
+        None => local,
+    };
+
+    let Some(record) = winner else {
+        return Ok(None);
+    };
+
+    // Adopt whichever side won into the local store so the next push
+    // compares against it rather than a stale local version.
+    local_store.push(&key, &record).await?;
+
+    decrypt_secret(&secrets_key, &record.encrypted).map(Some)
+}
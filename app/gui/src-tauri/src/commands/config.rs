@@ -1,4 +1,6 @@
-use crate::config::{AppConfig, Network};
+use crate::commands::secrets::SecretsDir;
+use crate::config::{AppConfig, Network, NetworkDef};
+use crate::crypto::secrets::SecretsKey;
 use tauri::Manager;
 
 /// Get the currently configured network name
@@ -8,17 +10,12 @@ pub fn get_network(app_handle: tauri::AppHandle) -> Result<String, String> {
     Ok(config.network.to_string())
 }
 
-/// Set the network (requires app restart to take effect).
-/// Saves back to the bundled config.json in dev, or app data dir in prod.
-#[tauri::command]
-pub fn set_network(app_handle: tauri::AppHandle, network: String) -> Result<(), String> {
-    let new_network = match network.to_lowercase().as_str() {
-        "preprod" => Network::Preprod,
-        "mainnet" => Network::Mainnet,
-        _ => return Err(format!("Unknown network: {network}. Must be 'preprod' or 'mainnet'.")),
-    };
-
-    // Read current config, update network, and save
+/// Persist `new_network` as the active network (requires app restart to take
+/// effect), to the bundled config.json in dev or the app data dir in prod.
+/// Shared by `set_network` (built-in networks) and `set_custom_network`
+/// (`Network::Custom`) so both save through the exact same path-resolution
+/// logic instead of two copies drifting apart.
+fn save_network(app_handle: &tauri::AppHandle, new_network: Network) -> Result<(), String> {
     let config = app_handle.state::<AppConfig>();
     let mut updated = config.inner().clone();
     updated.network = new_network;
@@ -38,6 +35,31 @@ pub fn set_network(app_handle: tauri::AppHandle, network: String) -> Result<(),
     Ok(())
 }
 
+/// Set the network to one of the two built-in networks (requires app
+/// restart to take effect). For a private testnet, a local devnet, or any
+/// public network not built into the binary, use `set_custom_network`
+/// instead — this command only ever produces `Network::Preprod`/`Mainnet`.
+#[tauri::command]
+pub fn set_network(app_handle: tauri::AppHandle, network: String) -> Result<(), String> {
+    let new_network = match network.to_lowercase().as_str() {
+        "preprod" => Network::Preprod,
+        "mainnet" => Network::Mainnet,
+        _ => return Err(format!("Unknown network: {network}. Must be 'preprod' or 'mainnet'.")),
+    };
+
+    save_network(&app_handle, new_network)
+}
+
+/// Set the network to a user-supplied `Network::Custom(def)` (requires app
+/// restart to take effect) — the real surface for "point the app at a
+/// private testnet, a local devnet, or a new public network without
+/// recompiling" that `Network::Custom` exists for, since `set_network`
+/// only ever accepts the two built-in names.
+#[tauri::command]
+pub fn set_custom_network(app_handle: tauri::AppHandle, def: NetworkDef) -> Result<(), String> {
+    save_network(&app_handle, Network::Custom(def))
+}
+
 /// Get the app data directory path
 #[tauri::command]
 pub fn get_data_dir(app_handle: tauri::AppHandle) -> Result<String, String> {
@@ -63,11 +85,19 @@ pub struct DiskUsage {
     pub wallet_bytes: u64,
     pub total_bytes: u64,
     pub data_dir: String,
+    /// Bytes sitting in accept-bid secrets whose TTL has already elapsed —
+    /// reclaimable via `commands::secrets::prune_expired_accept_bid_secrets`.
+    /// `0` if the wallet is locked (expiry can't be checked without
+    /// decrypting each entry).
+    pub expired_secret_bytes: u64,
 }
 
-/// Get disk usage for chain data, SNARK files, and wallet
+/// Get disk usage for chain data, SNARK files, wallet, and expired secrets
 #[tauri::command]
-pub fn get_disk_usage(app_handle: tauri::AppHandle) -> Result<DiskUsage, String> {
+pub fn get_disk_usage(
+    app_handle: tauri::AppHandle,
+    key_state: tauri::State<'_, SecretsKey>,
+) -> Result<DiskUsage, String> {
     let app_data_dir = app_handle
         .path()
         .app_data_dir()
@@ -77,10 +107,15 @@ pub fn get_disk_usage(app_handle: tauri::AppHandle) -> Result<DiskUsage, String>
     let chain_dir = app_data_dir.join(config.network.to_string());
     let snark_dir = app_data_dir.join("snark");
     let wallet_path = app_data_dir.join("wallet.json");
+    let secrets_dir = app_handle.state::<SecretsDir>();
 
     let chain_data_bytes = dir_size(&chain_dir);
     let snark_data_bytes = dir_size(&snark_dir);
     let wallet_bytes = wallet_path.metadata().map(|m| m.len()).unwrap_or(0);
+    let expired_secret_bytes = key_state
+        .get()
+        .map(|key| crate::commands::secrets::expired_accept_bid_bytes(&secrets_dir.0, &key, 0))
+        .unwrap_or(0);
     let total_bytes = chain_data_bytes + snark_data_bytes + wallet_bytes;
 
     Ok(DiskUsage {
@@ -89,6 +124,7 @@ pub fn get_disk_usage(app_handle: tauri::AppHandle) -> Result<DiskUsage, String>
         wallet_bytes,
         total_bytes,
         data_dir: app_data_dir.to_string_lossy().into(),
+        expired_secret_bytes,
     })
 }
 
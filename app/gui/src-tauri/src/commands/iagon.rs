@@ -1,29 +1,87 @@
 use crate::crypto::secrets::{secure_delete, SecretsKey};
+use futures_util::StreamExt;
+use rand::RngCore;
 use std::path::Path;
+use std::sync::Mutex;
 
 use super::secrets::SecretsDir;
 
-use crate::crypto::secrets::{decrypt_secret, encrypt_secret, EncryptedSecret};
+use crate::crypto::secrets::{decrypt_secret, encrypt_secret_v2, EncryptedSecret, NonceSequence};
 
 // ── Constants ────────────────────────────────────────────────────────────
 
-const IAGON_BASE: &str = "https://gw.iagon.com/api/v2";
+const IAGON_HOST: &str = "https://gw.iagon.com/api";
+/// The API version this build was written against. `iagon_check_api_version`
+/// compares this against whatever Iagon actually serves, so a version bump on
+/// their end shows up as a clear warning instead of every command quietly
+/// 404ing the moment the old prefix stops resolving.
+const EXPECTED_API_VERSION: &str = "v2";
+
+/// The API version negotiated by `iagon_check_api_version`, if it's been run.
+/// Every other Iagon command reads `base_url()` to build its request URL
+/// rather than formatting in a hardcoded version, so a detected version bump
+/// takes effect everywhere at once instead of requiring an edit to every
+/// `format!` in this file.
+pub struct IagonApiVersion(Mutex<Option<String>>);
+
+impl IagonApiVersion {
+    pub fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    /// The base URL to build requests against: the last version
+    /// `iagon_check_api_version` observed Iagon actually serving, or
+    /// `EXPECTED_API_VERSION` if it hasn't been run yet.
+    fn base_url(&self) -> Result<String, String> {
+        let version = self
+            .0
+            .lock()
+            .map_err(|_| "Internal error: Iagon API version lock poisoned".to_string())?
+            .clone()
+            .unwrap_or_else(|| EXPECTED_API_VERSION.to_string());
+        Ok(format!("{IAGON_HOST}/{version}"))
+    }
+
+    /// A clearer substitute for the generic "endpoint not found" message when
+    /// a 404 comes back: if the last check found Iagon serving a different
+    /// version than this build targets, say so explicitly instead of leaving
+    /// the user to guess.
+    fn not_found_message(&self) -> String {
+        let negotiated = match self.0.lock() {
+            Ok(guard) => guard.clone(),
+            Err(_) => return "Internal error: Iagon API version lock poisoned".to_string(),
+        };
+        match negotiated {
+            Some(negotiated) if negotiated != EXPECTED_API_VERSION => format!(
+                "Iagon is now serving API {negotiated}; this build still targets {EXPECTED_API_VERSION}. Run an API version check and update the client."
+            ),
+            _ => "Iagon endpoint not found. The API may have changed — run an API version check."
+                .to_string(),
+        }
+    }
+}
+
+impl Default for IagonApiVersion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 // ── Helpers ─────────────────────────────────────────────────────────────
 
 fn get_secrets_key(key_state: &SecretsKey) -> Result<[u8; 32], String> {
-    let guard = key_state
-        .0
-        .lock()
-        .map_err(|_| "Internal error: secrets key lock poisoned".to_string())?;
-    match *guard {
-        Some(key) => Ok(key),
-        None => Err("Wallet is locked — unlock to access secrets".to_string()),
-    }
+    key_state.get()
 }
 
-fn encrypt_and_write(key: &[u8; 32], path: &Path, data: &[u8]) -> Result<(), String> {
-    let encrypted = encrypt_secret(key, data)?;
+/// Filename (relative to a `SecretsDir` base) of the nonce counter every
+/// v2-encrypting call site in this vault shares — same counter
+/// `commands::secrets` rotates through, so no two secrets under the same
+/// key ever draw the same nonce no matter which command wrote them.
+const NONCE_COUNTER_FILENAME: &str = "nonce_counter";
+
+fn encrypt_and_write(key: &[u8; 32], base: &Path, path: &Path, data: &[u8]) -> Result<(), String> {
+    let nonce_seq = NonceSequence::new(base.join(NONCE_COUNTER_FILENAME));
+    let encrypted = encrypt_secret_v2(key, data, &nonce_seq)?;
     let json = serde_json::to_string_pretty(&encrypted)
         .map_err(|e| format!("Failed to serialize encrypted secret: {e}"))?;
     std::fs::write(path, json).map_err(|e| format!("Failed to write secret: {e}"))?;
@@ -41,20 +99,242 @@ fn iagon_dir(base: &Path) -> std::path::PathBuf {
     base.join("iagon")
 }
 
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// One audit line for `iagon_upload`/`iagon_download`/`iagon_delete_file` —
+/// recorded regardless of outcome, so a failed transfer shows up in the
+/// history just as much as a successful one.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct IagonAuditEntry {
+    pub timestamp: i64,
+    pub operation: String,
+    pub file_id: Option<String>,
+    pub filename: Option<String>,
+    pub bytes: u64,
+    pub status: Option<u16>,
+    pub success: bool,
+}
+
+impl IagonAuditEntry {
+    fn new(
+        operation: &str,
+        file_id: Option<String>,
+        filename: Option<String>,
+        bytes: u64,
+        status: Option<u16>,
+        success: bool,
+    ) -> Self {
+        Self {
+            timestamp: now_secs(),
+            operation: operation.to_string(),
+            file_id,
+            filename,
+            bytes,
+            status,
+            success,
+        }
+    }
+}
+
+const AUDIT_LOG_NAME: &str = "audit";
+/// Smaller cap than a process's stdout/stderr log (`DEFAULT_MAX_BYTES`) —
+/// one line per transfer, so this grows far slower.
+const AUDIT_LOG_MAX_BYTES: u64 = 2 * 1024 * 1024;
+const AUDIT_LOG_MAX_ARCHIVES: usize = 3;
+
+fn audit_log_dir(base: &Path) -> std::path::PathBuf {
+    iagon_dir(base).join("audit")
+}
+
+/// Append one audit entry, rotating the log if it's grown past
+/// `AUDIT_LOG_MAX_BYTES`. Best-effort: a failure to write the audit log
+/// shouldn't fail the upload/download/delete it's describing.
+fn record_audit_entry(base: &Path, entry: IagonAuditEntry) {
+    let line = match serde_json::to_string(&entry) {
+        Ok(line) => line,
+        Err(e) => {
+            eprintln!("[iagon-audit] Failed to serialize audit entry: {e}");
+            return;
+        }
+    };
+
+    let dir = audit_log_dir(base);
+    match crate::process::rotating_log::RotatingLogWriter::open_with_limits(
+        &dir,
+        AUDIT_LOG_NAME,
+        AUDIT_LOG_MAX_BYTES,
+        AUDIT_LOG_MAX_ARCHIVES,
+    ) {
+        Ok(mut writer) => {
+            if let Err(e) = writer.write_line(&line) {
+                eprintln!("[iagon-audit] Failed to write audit entry: {e}");
+            }
+        }
+        Err(e) => eprintln!("[iagon-audit] Failed to open audit log: {e}"),
+    }
+}
+
+/// Read back the most recent `limit` audit entries across the live log and
+/// its rotated archives, oldest first — mirrors `get_process_logs`'
+/// archive-paging convention but flattened into one combined, chronological
+/// page instead of requiring the caller to page through archives one at a
+/// time.
+#[tauri::command]
+pub fn get_iagon_audit_log(
+    state: tauri::State<'_, SecretsDir>,
+    limit: usize,
+) -> Result<Vec<IagonAuditEntry>, String> {
+    let dir = audit_log_dir(&state.0);
+    let mut lines: Vec<String> = Vec::new();
+
+    for archive in (1..=AUDIT_LOG_MAX_ARCHIVES).rev() {
+        if let Ok(archived) =
+            crate::process::rotating_log::read_logs(&dir, AUDIT_LOG_NAME, Some(archive), None)
+        {
+            lines.extend(archived);
+        }
+    }
+    if let Ok(current) =
+        crate::process::rotating_log::read_logs(&dir, AUDIT_LOG_NAME, None, None)
+    {
+        lines.extend(current);
+    }
+
+    let start = lines.len().saturating_sub(limit);
+    lines[start..]
+        .iter()
+        .map(|line| serde_json::from_str(line).map_err(|e| format!("Invalid audit entry: {e}")))
+        .collect()
+}
+
+#[tauri::command]
+pub fn clear_iagon_audit_log(state: tauri::State<'_, SecretsDir>) -> Result<(), String> {
+    let dir = audit_log_dir(&state.0);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)
+            .map_err(|e| format!("Failed to clear Iagon audit log: {e}"))?;
+    }
+    Ok(())
+}
+
 const API_KEY_FILENAME: &str = "api_key.json";
 
-fn build_client() -> Result<reqwest::Client, String> {
-    reqwest::Client::builder()
+/// How long an idle pooled connection is kept open before reqwest closes it.
+const POOL_IDLE_TIMEOUT_SECS: u64 = 90;
+/// Cap per-host idle connections so a burst of uploads/downloads doesn't pin
+/// an unbounded number of sockets open between calls.
+const POOL_MAX_IDLE_PER_HOST: usize = 8;
+
+/// Proxy and DNS-pinning settings for the Iagon transport, stored alongside
+/// the encrypted API key. Neither field is sensitive (a proxy URL or
+/// resolver address isn't secret material the way the API key is), so this
+/// is plain JSON rather than going through `encrypt_and_write`.
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct IagonTransportConfig {
+    /// A SOCKS5 or HTTP proxy URL (e.g. `socks5://127.0.0.1:9050` for Tor),
+    /// applied to all Iagon requests via `reqwest::Proxy::all`.
+    #[serde(default)]
+    pub proxy_url: Option<String>,
+    /// Pin `gw.iagon.com` to this `ip:port` socket address, bypassing the system
+    /// resolver, so DNS lookups for Iagon traffic aren't leaked to the
+    /// local network.
+    #[serde(default)]
+    pub dns_resolver: Option<String>,
+}
+
+const TRANSPORT_CONFIG_FILENAME: &str = "transport.json";
+
+#[tauri::command]
+pub fn get_iagon_transport_config(
+    state: tauri::State<'_, SecretsDir>,
+) -> Result<IagonTransportConfig, String> {
+    load_transport_config(&state.0)
+}
+
+#[tauri::command]
+pub fn set_iagon_transport_config(
+    state: tauri::State<'_, SecretsDir>,
+    config: IagonTransportConfig,
+) -> Result<(), String> {
+    // Reject anything build_client() itself can't turn into a client before
+    // ever writing it to disk — IagonHttp::new() calls build_client().expect(...)
+    // on whatever's saved here at the next startup, so a bad proxy URL or
+    // dns_resolver saved unvalidated would panic the whole app before it
+    // gets far enough to load any UI the user could use to fix it.
+    build_client(&config)?;
+
+    let dir = iagon_dir(&state.0);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create iagon secrets dir: {e}"))?;
+    let json = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize transport config: {e}"))?;
+    std::fs::write(dir.join(TRANSPORT_CONFIG_FILENAME), json)
+        .map_err(|e| format!("Failed to write transport config: {e}"))
+}
+
+fn load_transport_config(base: &Path) -> Result<IagonTransportConfig, String> {
+    let path = iagon_dir(base).join(TRANSPORT_CONFIG_FILENAME);
+    if !path.exists() {
+        return Ok(IagonTransportConfig::default());
+    }
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read transport config: {e}"))?;
+    serde_json::from_str(&json).map_err(|e| format!("Invalid transport config: {e}"))
+}
+
+/// Long-lived `reqwest::Client`, built once at startup and handed to every
+/// Iagon command as managed state. A `reqwest::Client` owns its connection
+/// pool and TLS session cache internally (it's cheap to clone, expensive to
+/// rebuild), so constructing a fresh one per call — as every command used to
+/// do via `build_client()` — threw away keep-alive connections and forced a
+/// new TLS handshake on every request.
+pub struct IagonHttp(pub reqwest::Client);
+
+impl IagonHttp {
+    /// `secrets_dir` is the same directory `SecretsDir` manages — read here
+    /// (synchronously, at startup, before any async runtime work) so a
+    /// configured proxy/resolver takes effect on the very first request
+    /// rather than requiring a restart after a later reload.
+    pub fn new(secrets_dir: &Path) -> Self {
+        let transport = load_transport_config(secrets_dir).unwrap_or_default();
+        Self(build_client(&transport).expect("Failed to build the shared Iagon HTTP client"))
+    }
+}
+
+fn build_client(transport: &IagonTransportConfig) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(60))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {e}"))
+        .pool_idle_timeout(std::time::Duration::from_secs(POOL_IDLE_TIMEOUT_SECS))
+        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST);
+
+    if let Some(proxy_url) = &transport.proxy_url {
+        let proxy = reqwest::Proxy::all(proxy_url).map_err(|e| format!("Invalid Iagon proxy URL: {e}"))?;
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(resolver) = &transport.dns_resolver {
+        let addr: std::net::SocketAddr = resolver
+            .parse()
+            .map_err(|e| format!("Invalid Iagon DNS resolver address (expected ip:port): {e}"))?;
+        let host = reqwest::Url::parse(IAGON_HOST)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+            .ok_or_else(|| "Failed to parse the Iagon host to pin".to_string())?;
+        builder = builder.resolve(&host, addr);
+    }
+
+    builder.build().map_err(|e| format!("Failed to create HTTP client: {e}"))
 }
 
 /// Map a reqwest error or non-2xx status into a user-friendly message.
-fn map_iagon_error(status: reqwest::StatusCode, body: &str) -> String {
+fn map_iagon_error(status: reqwest::StatusCode, body: &str, version: &IagonApiVersion) -> String {
     match status.as_u16() {
         401 | 403 => "Authentication failed. Your API key may be expired or invalid.".to_string(),
-        404 => "Iagon endpoint not found. The API may have changed.".to_string(),
+        404 => version.not_found_message(),
         500..=599 => format!("Iagon server error ({status}). Try again later."),
         _ => {
             // Try to extract a message from JSON body
@@ -78,6 +358,53 @@ fn map_reqwest_error(e: reqwest::Error) -> String {
     }
 }
 
+/// Retry a transient failure — a connect/timeout error, or a 5xx status —
+/// this many times before giving up. 4xx statuses (bad API key, not found)
+/// are deterministic, so they're returned on the first attempt.
+const MAX_RETRY_ATTEMPTS: u32 = 3;
+/// Backoff schedule before a retry: roughly 250ms, 500ms, 1s, each ±25%
+/// jitter so a burst of retrying clients doesn't resync into a thundering
+/// herd against the same endpoint.
+const RETRY_BASE_DELAY_MS: u64 = 250;
+const RETRY_MAX_DELAY_MS: u64 = 1000;
+
+fn retry_delay(attempt: u32) -> std::time::Duration {
+    let base = RETRY_BASE_DELAY_MS
+        .saturating_mul(1u64 << attempt.min(4))
+        .min(RETRY_MAX_DELAY_MS);
+    let jitter_range = base / 4;
+    let mut buf = [0u8; 4];
+    rand::rngs::OsRng.fill_bytes(&mut buf);
+    let jitter = (u32::from_le_bytes(buf) as u64) % (jitter_range * 2 + 1);
+    std::time::Duration::from_millis(base - jitter_range + jitter)
+}
+
+/// Retry an Iagon request built fresh by `build_request` on each attempt —
+/// rebuilding rather than cloning a `reqwest::RequestBuilder`, since it
+/// isn't `Clone`. Callers whose request body can't be cheaply rebuilt (a
+/// streamed file upload, say) should call `.send()` directly instead of
+/// going through this wrapper.
+async fn send_with_retry<F, Fut>(mut build_request: F) -> Result<reqwest::Response, reqwest::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<reqwest::Response, reqwest::Error>>,
+{
+    for attempt in 0..=MAX_RETRY_ATTEMPTS {
+        let result = build_request().await;
+        let retryable = match &result {
+            Ok(res) => res.status().is_server_error(),
+            Err(e) => e.is_timeout() || e.is_connect(),
+        };
+
+        if !retryable || attempt >= MAX_RETRY_ATTEMPTS {
+            return result;
+        }
+
+        tokio::time::sleep(retry_delay(attempt)).await;
+    }
+    unreachable!("the loop above always returns before exhausting MAX_RETRY_ATTEMPTS")
+}
+
 // ── Iagon API key storage ───────────────────────────────────────────────
 
 #[tauri::command]
@@ -90,7 +417,7 @@ pub fn store_iagon_api_key(
     let dir = iagon_dir(&state.0);
     std::fs::create_dir_all(&dir)
         .map_err(|e| format!("Failed to create iagon secrets dir: {e}"))?;
-    encrypt_and_write(&key, &dir.join(API_KEY_FILENAME), api_key.as_bytes())
+    encrypt_and_write(&key, &state.0, &dir.join(API_KEY_FILENAME), api_key.as_bytes())
 }
 
 #[tauri::command]
@@ -124,19 +451,26 @@ pub fn has_iagon_api_key(state: tauri::State<'_, SecretsDir>) -> Result<bool, St
 // ── Iagon HTTP proxy commands (bypass CORS) ─────────────────────────────
 
 #[tauri::command]
-pub async fn iagon_get_nonce(address: String) -> Result<String, String> {
-    let client = build_client()?;
-    let res = client
-        .post(format!("{IAGON_BASE}/public/nonce"))
-        .json(&serde_json::json!({ "publicAddress": address }))
-        .send()
-        .await
-        .map_err(map_reqwest_error)?;
+pub async fn iagon_get_nonce(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
+    address: String,
+) -> Result<String, String> {
+    let client = &http.0;
+    let base = version.base_url()?;
+    let res = send_with_retry(|| {
+        client
+            .post(format!("{base}/public/nonce"))
+            .json(&serde_json::json!({ "publicAddress": address }))
+            .send()
+    })
+    .await
+    .map_err(map_reqwest_error)?;
 
     let status = res.status();
     let body = res.text().await.unwrap_or_default();
     if !status.is_success() {
-        return Err(map_iagon_error(status, &body));
+        return Err(map_iagon_error(status, &body, &version));
     }
     let v: serde_json::Value =
         serde_json::from_str(&body).map_err(|e| format!("Invalid response from Iagon: {e}"))?;
@@ -154,26 +488,31 @@ pub struct IagonVerifyResult {
 
 #[tauri::command]
 pub async fn iagon_verify(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
     address: String,
     signature: String,
     key: String,
 ) -> Result<IagonVerifyResult, String> {
-    let client = build_client()?;
-    let res = client
-        .post(format!("{IAGON_BASE}/public/verify"))
-        .json(&serde_json::json!({
-            "publicAddress": address,
-            "signature": signature,
-            "key": key,
-        }))
-        .send()
-        .await
-        .map_err(map_reqwest_error)?;
+    let client = &http.0;
+    let base = version.base_url()?;
+    let res = send_with_retry(|| {
+        client
+            .post(format!("{base}/public/verify"))
+            .json(&serde_json::json!({
+                "publicAddress": address,
+                "signature": signature,
+                "key": key,
+            }))
+            .send()
+    })
+    .await
+    .map_err(map_reqwest_error)?;
 
     let status = res.status();
     let body = res.text().await.unwrap_or_default();
     if !status.is_success() {
-        return Err(map_iagon_error(status, &body));
+        return Err(map_iagon_error(status, &body, &version));
     }
     let v: serde_json::Value =
         serde_json::from_str(&body).map_err(|e| format!("Invalid response from Iagon: {e}"))?;
@@ -191,20 +530,28 @@ pub async fn iagon_verify(
 }
 
 #[tauri::command]
-pub async fn iagon_generate_api_key(session_token: String, name: String) -> Result<String, String> {
-    let client = build_client()?;
-    let res = client
-        .post(format!("{IAGON_BASE}/key/generate"))
-        .header("Authorization", format!("Bearer {session_token}"))
-        .json(&serde_json::json!({ "api_key_name": name }))
-        .send()
-        .await
-        .map_err(map_reqwest_error)?;
+pub async fn iagon_generate_api_key(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
+    session_token: String,
+    name: String,
+) -> Result<String, String> {
+    let client = &http.0;
+    let base = version.base_url()?;
+    let res = send_with_retry(|| {
+        client
+            .post(format!("{base}/key/generate"))
+            .header("Authorization", format!("Bearer {session_token}"))
+            .json(&serde_json::json!({ "api_key_name": name }))
+            .send()
+    })
+    .await
+    .map_err(map_reqwest_error)?;
 
     let status = res.status();
     let body = res.text().await.unwrap_or_default();
     if !status.is_success() {
-        return Err(map_iagon_error(status, &body));
+        return Err(map_iagon_error(status, &body, &version));
     }
     let v: serde_json::Value =
         serde_json::from_str(&body).map_err(|e| format!("Invalid response from Iagon: {e}"))?;
@@ -222,14 +569,21 @@ pub async fn iagon_generate_api_key(session_token: String, name: String) -> Resu
 }
 
 #[tauri::command]
-pub async fn iagon_verify_api_key(api_key: String) -> Result<bool, String> {
-    let client = build_client()?;
-    let res = client
-        .post(format!("{IAGON_BASE}/key/verify"))
-        .json(&serde_json::json!({ "api_key": api_key }))
-        .send()
-        .await
-        .map_err(map_reqwest_error)?;
+pub async fn iagon_verify_api_key(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
+    api_key: String,
+) -> Result<bool, String> {
+    let client = &http.0;
+    let base = version.base_url()?;
+    let res = send_with_retry(|| {
+        client
+            .post(format!("{base}/key/verify"))
+            .json(&serde_json::json!({ "api_key": api_key }))
+            .send()
+    })
+    .await
+    .map_err(map_reqwest_error)?;
 
     if !res.status().is_success() {
         return Ok(false);
@@ -252,37 +606,87 @@ pub struct IagonFileInfo {
     pub file_size_byte_native: u64,
     #[serde(default)]
     pub file_size_byte_encrypted: u64,
+    /// Set by `iagon_upload_encrypted` after a successful upload — not part
+    /// of Iagon's own response, so it always defaults to `false` for plain
+    /// `iagon_upload` results. Lets the frontend tell a confidential backup
+    /// apart from a public one without having to remember which command
+    /// produced it.
+    #[serde(default)]
+    pub encrypted: bool,
 }
 
-#[tauri::command]
-pub async fn iagon_upload(
-    api_key: String,
+/// Shared upload implementation behind both `iagon_upload` (plaintext,
+/// public) and `iagon_upload_encrypted` (ciphertext bytes, still uploaded
+/// with `visibility: "public"` — the data itself is what's confidential).
+/// `file_data` is cheap to clone, so the whole request is rebuilt and
+/// retried by `send_with_retry` on a transient failure. Records one audit
+/// entry under `audit_base` regardless of outcome.
+async fn upload_bytes(
+    client: &reqwest::Client,
+    version: &IagonApiVersion,
+    api_key: &str,
     file_data: Vec<u8>,
     filename: String,
+    audit_base: &Path,
 ) -> Result<IagonFileInfo, String> {
-    let client = build_client()?;
-    let part = reqwest::multipart::Part::bytes(file_data)
-        .file_name(filename.clone())
-        .mime_str("application/octet-stream")
-        .map_err(|e| format!("Failed to create upload part: {e}"))?;
+    let data_len = file_data.len() as u64;
+    let base = version.base_url()?;
 
-    let form = reqwest::multipart::Form::new()
-        .part("file", part)
-        .text("filename", filename)
-        .text("visibility", "public");
+    let res = match send_with_retry(|| {
+        let part = reqwest::multipart::Part::bytes(file_data.clone())
+            .file_name(filename.clone())
+            .mime_str("application/octet-stream")
+            .expect("\"application/octet-stream\" is always a valid mime type");
+        let form = reqwest::multipart::Form::new()
+            .part("file", part)
+            .text("filename", filename.clone())
+            .text("visibility", "public");
 
-    let res = client
-        .post(format!("{IAGON_BASE}/storage/upload"))
-        .header("x-api-key", &api_key)
-        .multipart(form)
-        .send()
-        .await
-        .map_err(map_reqwest_error)?;
+        client
+            .post(format!("{base}/storage/upload"))
+            .header("x-api-key", api_key)
+            .multipart(form)
+            .send()
+    })
+    .await
+    {
+        Ok(res) => res,
+        Err(e) => {
+            record_audit_entry(
+                audit_base,
+                IagonAuditEntry::new("upload", None, Some(filename), data_len, None, false),
+            );
+            return Err(map_reqwest_error(e));
+        }
+    };
+
+    let status = res.status().as_u16();
+    let result = parse_upload_response(res, version).await;
+    record_audit_entry(
+        audit_base,
+        IagonAuditEntry::new(
+            "upload",
+            result.as_ref().ok().map(|info| info._id.clone()),
+            Some(filename),
+            data_len,
+            Some(status),
+            result.is_ok(),
+        ),
+    );
+    result
+}
 
+/// Parse an upload response into `IagonFileInfo`. Shared by `upload_bytes`
+/// (retried) and `iagon_upload_path` (streamed, sent once — its body can't
+/// be cheaply rebuilt for a retry).
+async fn parse_upload_response(
+    res: reqwest::Response,
+    version: &IagonApiVersion,
+) -> Result<IagonFileInfo, String> {
     let status = res.status();
     let body = res.text().await.unwrap_or_default();
     if !status.is_success() {
-        return Err(map_iagon_error(status, &body));
+        return Err(map_iagon_error(status, &body, version));
     }
     let v: serde_json::Value =
         serde_json::from_str(&body).map_err(|e| format!("Invalid upload response: {e}"))?;
@@ -299,44 +703,513 @@ pub async fn iagon_upload(
     serde_json::from_value(data.clone()).map_err(|e| format!("Failed to parse upload result: {e}"))
 }
 
-#[tauri::command]
-pub async fn iagon_download(api_key: String, file_id: String) -> Result<Vec<u8>, String> {
-    let client = build_client()?;
-    let form = reqwest::multipart::Form::new().text("id", file_id);
-
+async fn send_upload(
+    client: &reqwest::Client,
+    version: &IagonApiVersion,
+    api_key: &str,
+    form: reqwest::multipart::Form,
+) -> Result<IagonFileInfo, String> {
+    let base = version.base_url()?;
     let res = client
-        .post(format!("{IAGON_BASE}/storage/download"))
-        .header("x-api-key", &api_key)
+        .post(format!("{base}/storage/upload"))
+        .header("x-api-key", api_key)
         .multipart(form)
         .send()
         .await
         .map_err(map_reqwest_error)?;
 
+    parse_upload_response(res, version).await
+}
+
+/// Shared download implementation behind both `iagon_download` (plaintext)
+/// and `iagon_download_encrypted` (ciphertext, decrypted after fetching).
+/// `file_id` is cheap to clone, so the whole request is rebuilt and retried
+/// by `send_with_retry` on a transient failure. Records one audit entry
+/// under `audit_base` regardless of outcome.
+async fn download_bytes(
+    client: &reqwest::Client,
+    version: &IagonApiVersion,
+    api_key: &str,
+    file_id: &str,
+    audit_base: &Path,
+) -> Result<Vec<u8>, String> {
+    let base = version.base_url()?;
+    let res = match send_with_retry(|| {
+        let form = reqwest::multipart::Form::new().text("id", file_id.to_string());
+        client
+            .post(format!("{base}/storage/download"))
+            .header("x-api-key", api_key)
+            .multipart(form)
+            .send()
+    })
+    .await
+    {
+        Ok(res) => res,
+        Err(e) => {
+            record_audit_entry(
+                audit_base,
+                IagonAuditEntry::new("download", Some(file_id.to_string()), None, 0, None, false),
+            );
+            return Err(map_reqwest_error(e));
+        }
+    };
+
     let status = res.status();
     if !status.is_success() {
         let body = res.text().await.unwrap_or_default();
-        return Err(map_iagon_error(status, &body));
+        record_audit_entry(
+            audit_base,
+            IagonAuditEntry::new(
+                "download",
+                Some(file_id.to_string()),
+                None,
+                0,
+                Some(status.as_u16()),
+                false,
+            ),
+        );
+        return Err(map_iagon_error(status, &body, version));
     }
-    res.bytes()
+
+    let bytes = res
+        .bytes()
         .await
         .map(|b| b.to_vec())
-        .map_err(|e| format!("Failed to read download response: {e}"))
+        .map_err(|e| format!("Failed to read download response: {e}"));
+    record_audit_entry(
+        audit_base,
+        IagonAuditEntry::new(
+            "download",
+            Some(file_id.to_string()),
+            None,
+            bytes.as_ref().map(|b| b.len() as u64).unwrap_or(0),
+            Some(status.as_u16()),
+            bytes.is_ok(),
+        ),
+    );
+    bytes
 }
 
 #[tauri::command]
-pub async fn iagon_delete_file(api_key: String, file_id: String) -> Result<(), String> {
-    let client = build_client()?;
-    let res = client
-        .delete(format!("{IAGON_BASE}/storage/file/{file_id}"))
+pub async fn iagon_upload(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
+    secrets_dir: tauri::State<'_, SecretsDir>,
+    api_key: String,
+    file_data: Vec<u8>,
+    filename: String,
+) -> Result<IagonFileInfo, String> {
+    upload_bytes(&http.0, &version, &api_key, file_data, filename, &secrets_dir.0).await
+}
+
+/// Encrypt `file_data` with the unlocked secrets key before uploading, so
+/// Iagon (and anyone who learns the resulting file id) only ever sees
+/// ciphertext — confidential remote backups on a storage tier that's
+/// otherwise public-by-default. The ciphertext is the serialized
+/// `EncryptedSecret` (nonce + ciphertext, JSON) rather than raw AES output,
+/// so `iagon_download_encrypted` can parse it back out symmetrically.
+#[tauri::command]
+pub async fn iagon_upload_encrypted(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
+    secrets_dir: tauri::State<'_, SecretsDir>,
+    key_state: tauri::State<'_, SecretsKey>,
+    api_key: String,
+    file_data: Vec<u8>,
+    filename: String,
+) -> Result<IagonFileInfo, String> {
+    let key = get_secrets_key(&key_state)?;
+    let nonce_seq = NonceSequence::new(secrets_dir.0.join(NONCE_COUNTER_FILENAME));
+    let encrypted = encrypt_secret_v2(&key, &file_data, &nonce_seq)?;
+    let payload = serde_json::to_vec(&encrypted)
+        .map_err(|e| format!("Failed to serialize encrypted upload: {e}"))?;
+
+    let mut info = upload_bytes(&http.0, &version, &api_key, payload, filename, &secrets_dir.0).await?;
+    info.encrypted = true;
+    Ok(info)
+}
+
+#[tauri::command]
+pub async fn iagon_download(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
+    secrets_dir: tauri::State<'_, SecretsDir>,
+    api_key: String,
+    file_id: String,
+) -> Result<Vec<u8>, String> {
+    download_bytes(&http.0, &version, &api_key, &file_id, &secrets_dir.0).await
+}
+
+/// Symmetric counterpart to `iagon_upload_encrypted`: fetch the bytes,
+/// parse them back into an `EncryptedSecret`, and decrypt with the unlocked
+/// secrets key. Fails with "Wallet is locked" (via `get_secrets_key`) rather
+/// than a confusing parse error if the wallet isn't unlocked — checked
+/// before the network round-trip so a locked wallet doesn't even attempt
+/// the download.
+#[tauri::command]
+pub async fn iagon_download_encrypted(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
+    secrets_dir: tauri::State<'_, SecretsDir>,
+    key_state: tauri::State<'_, SecretsKey>,
+    api_key: String,
+    file_id: String,
+) -> Result<Vec<u8>, String> {
+    let key = get_secrets_key(&key_state)?;
+    let bytes = download_bytes(&http.0, &version, &api_key, &file_id, &secrets_dir.0).await?;
+    let encrypted: EncryptedSecret = serde_json::from_slice(&bytes)
+        .map_err(|e| format!("Downloaded object is not a valid encrypted upload: {e}"))?;
+    decrypt_secret(&key, &encrypted)
+}
+
+#[tauri::command]
+pub async fn iagon_delete_file(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
+    secrets_dir: tauri::State<'_, SecretsDir>,
+    api_key: String,
+    file_id: String,
+) -> Result<(), String> {
+    let client = &http.0;
+    let base = version.base_url()?;
+    let res = match send_with_retry(|| {
+        client
+            .delete(format!("{base}/storage/file/{file_id}"))
+            .header("x-api-key", &api_key)
+            .send()
+    })
+    .await
+    {
+        Ok(res) => res,
+        Err(e) => {
+            record_audit_entry(
+                &secrets_dir.0,
+                IagonAuditEntry::new("delete", Some(file_id), None, 0, None, false),
+            );
+            return Err(map_reqwest_error(e));
+        }
+    };
+
+    let status = res.status();
+    if !status.is_success() {
+        let body = res.text().await.unwrap_or_default();
+        record_audit_entry(
+            &secrets_dir.0,
+            IagonAuditEntry::new("delete", Some(file_id), None, 0, Some(status.as_u16()), false),
+        );
+        return Err(map_iagon_error(status, &body, &version));
+    }
+
+    record_audit_entry(
+        &secrets_dir.0,
+        IagonAuditEntry::new("delete", Some(file_id), None, 0, Some(status.as_u16()), true),
+    );
+    Ok(())
+}
+
+/// Progress for a streaming upload/download, emitted as chunks flush so the
+/// frontend can show a live bar instead of the invoke hanging silently for
+/// the whole transfer.
+#[derive(Clone, serde::Serialize)]
+pub struct IagonTransferProgress {
+    pub bytes: u64,
+    pub total: u64,
+    pub percent: f64,
+}
+
+/// Stream `src_path` straight off disk into the multipart upload body,
+/// instead of reading the whole file into a `Vec<u8>` (as `iagon_upload`
+/// does) and handing a second copy to Tauri's IPC marshalling. Meant for
+/// multi-hundred-MB archives where buffering twice risks an OOM. Emits
+/// "iagon-upload-progress" events as each chunk is read.
+#[tauri::command]
+pub async fn iagon_upload_path(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
+    secrets_dir: tauri::State<'_, SecretsDir>,
+    app: tauri::AppHandle,
+    api_key: String,
+    src_path: String,
+    filename: String,
+) -> Result<IagonFileInfo, String> {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use tauri::Emitter;
+
+    let file = match tokio::fs::File::open(&src_path).await {
+        Ok(file) => file,
+        Err(e) => {
+            record_audit_entry(
+                &secrets_dir.0,
+                IagonAuditEntry::new("upload", None, Some(filename), 0, None, false),
+            );
+            return Err(format!("Failed to open {src_path}: {e}"));
+        }
+    };
+    let total = match file.metadata().await {
+        Ok(meta) => meta.len(),
+        Err(e) => {
+            record_audit_entry(
+                &secrets_dir.0,
+                IagonAuditEntry::new("upload", None, Some(filename), 0, None, false),
+            );
+            return Err(format!("Failed to stat {src_path}: {e}"));
+        }
+    };
+
+    let sent = Arc::new(AtomicU64::new(0));
+    let sent_for_stream = sent.clone();
+    let stream = tokio_util::io::ReaderStream::new(file).map(move |chunk| {
+        if let Ok(bytes) = &chunk {
+            let now = sent_for_stream.fetch_add(bytes.len() as u64, Ordering::Relaxed) + bytes.len() as u64;
+            let _ = app.emit(
+                "iagon-upload-progress",
+                IagonTransferProgress {
+                    bytes: now,
+                    total,
+                    percent: if total > 0 { (now as f64 / total as f64) * 100.0 } else { 100.0 },
+                },
+            );
+        }
+        chunk
+    });
+
+    let part = reqwest::multipart::Part::stream_with_length(reqwest::Body::wrap_stream(stream), total)
+        .file_name(filename.clone())
+        .mime_str("application/octet-stream")
+        .map_err(|e| format!("Failed to create upload part: {e}"))?;
+
+    let form = reqwest::multipart::Form::new()
+        .part("file", part)
+        .text("filename", filename.clone())
+        .text("visibility", "public");
+
+    let result = send_upload(&http.0, &version, &api_key, form).await;
+    record_audit_entry(
+        &secrets_dir.0,
+        IagonAuditEntry::new(
+            "upload",
+            result.as_ref().ok().map(|info| info._id.clone()),
+            Some(filename),
+            total,
+            None,
+            result.is_ok(),
+        ),
+    );
+    result
+}
+
+/// Stream the downloaded bytes straight to `dest_path` instead of buffering
+/// the whole file into a `Vec<u8>` (as `iagon_download` does) before it ever
+/// reaches disk. Emits "iagon-download-progress" events as each chunk
+/// arrives; `total` is 0 if Iagon doesn't send a `Content-Length`.
+#[tauri::command]
+pub async fn iagon_download_to_path(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
+    secrets_dir: tauri::State<'_, SecretsDir>,
+    app: tauri::AppHandle,
+    api_key: String,
+    file_id: String,
+    dest_path: String,
+) -> Result<(), String> {
+    use tauri::Emitter;
+    use tokio::io::AsyncWriteExt;
+
+    let client = &http.0;
+    let base = version.base_url()?;
+    let form = reqwest::multipart::Form::new().text("id", file_id.clone());
+
+    let res = match client
+        .post(format!("{base}/storage/download"))
         .header("x-api-key", &api_key)
+        .multipart(form)
         .send()
         .await
-        .map_err(map_reqwest_error)?;
+    {
+        Ok(res) => res,
+        Err(e) => {
+            record_audit_entry(
+                &secrets_dir.0,
+                IagonAuditEntry::new("download", Some(file_id), None, 0, None, false),
+            );
+            return Err(map_reqwest_error(e));
+        }
+    };
 
     let status = res.status();
     if !status.is_success() {
         let body = res.text().await.unwrap_or_default();
-        return Err(map_iagon_error(status, &body));
+        record_audit_entry(
+            &secrets_dir.0,
+            IagonAuditEntry::new(
+                "download",
+                Some(file_id),
+                None,
+                0,
+                Some(status.as_u16()),
+                false,
+            ),
+        );
+        return Err(map_iagon_error(status, &body, &version));
     }
-    Ok(())
+
+    let total = res.content_length().unwrap_or(0);
+    let mut out = match tokio::fs::File::create(&dest_path).await {
+        Ok(out) => out,
+        Err(e) => {
+            record_audit_entry(
+                &secrets_dir.0,
+                IagonAuditEntry::new("download", Some(file_id), None, 0, Some(status.as_u16()), false),
+            );
+            return Err(format!("Failed to create {dest_path}: {e}"));
+        }
+    };
+
+    let mut received: u64 = 0;
+    let mut stream = res.bytes_stream();
+    let result: Result<(), String> = loop {
+        let Some(chunk) = stream.next().await else {
+            break Ok(());
+        };
+        let chunk = match chunk.map_err(map_reqwest_error) {
+            Ok(chunk) => chunk,
+            Err(e) => break Err(e),
+        };
+        if let Err(e) = out
+            .write_all(&chunk)
+            .await
+            .map_err(|e| format!("Failed to write {dest_path}: {e}"))
+        {
+            break Err(e);
+        }
+        received += chunk.len() as u64;
+        let _ = app.emit(
+            "iagon-download-progress",
+            IagonTransferProgress {
+                bytes: received,
+                total,
+                percent: if total > 0 { (received as f64 / total as f64) * 100.0 } else { 0.0 },
+            },
+        );
+    };
+
+    record_audit_entry(
+        &secrets_dir.0,
+        IagonAuditEntry::new(
+            "download",
+            Some(file_id),
+            None,
+            received,
+            Some(status.as_u16()),
+            result.is_ok(),
+        ),
+    );
+    result
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct IagonConnectionTestResult {
+    pub success: bool,
+    pub latency_ms: u64,
+    pub message: String,
+}
+
+/// Send one request through the configured transport (proxy and/or pinned
+/// resolver, if set via `set_iagon_transport_config`) and report whether it
+/// got any HTTP response back, and how long that took — lets a user
+/// validate a SOCKS5/HTTP proxy or pinned resolver before relying on it for
+/// real uploads/downloads. Deliberately bypasses `send_with_retry`: a
+/// connectivity probe that silently retries past a failure isn't telling
+/// the user what they asked to know.
+#[tauri::command]
+pub async fn test_iagon_connection(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
+) -> Result<IagonConnectionTestResult, String> {
+    let client = &http.0;
+    let start = std::time::Instant::now();
+    let base = version.base_url()?;
+
+    Ok(match client.get(base).send().await {
+        Ok(res) => IagonConnectionTestResult {
+            success: true,
+            latency_ms: start.elapsed().as_millis() as u64,
+            message: format!("Reached Iagon ({})", res.status()),
+        },
+        Err(e) => IagonConnectionTestResult {
+            success: false,
+            latency_ms: start.elapsed().as_millis() as u64,
+            message: map_reqwest_error(e),
+        },
+    })
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct IagonApiVersionCheck {
+    pub expected: String,
+    pub negotiated: String,
+    pub matches_expected: bool,
+    pub message: String,
+}
+
+/// Probe the `key/verify` endpoint (answers even to a bogus key, so it's
+/// side-effect-free) against the version this build targets, and read back
+/// whatever version Iagon says it actually served, via an
+/// `x-iagon-api-version` response header — the same header-negotiation shape
+/// kanidm uses for its own API version checks. Stores the result in
+/// `IagonApiVersion` so every other command's request URL reflects reality
+/// from this point on, and so a later 404 can explain itself ("Iagon upgraded
+/// to vN") instead of just saying the endpoint is missing.
+#[tauri::command]
+pub async fn iagon_check_api_version(
+    http: tauri::State<'_, IagonHttp>,
+    version: tauri::State<'_, IagonApiVersion>,
+) -> Result<IagonApiVersionCheck, String> {
+    let client = &http.0;
+    let expected_base = format!("{IAGON_HOST}/{EXPECTED_API_VERSION}");
+
+    let res = client
+        .post(format!("{expected_base}/key/verify"))
+        .json(&serde_json::json!({ "api_key": "" }))
+        .send()
+        .await
+        .map_err(map_reqwest_error)?;
+
+    let reachable = res.status() != reqwest::StatusCode::NOT_FOUND;
+    let negotiated = res
+        .headers()
+        .get("x-iagon-api-version")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim_start_matches('v').to_string())
+        .map(|v| format!("v{v}"))
+        .unwrap_or_else(|| EXPECTED_API_VERSION.to_string());
+
+    if reachable {
+        *version
+            .0
+            .lock()
+            .map_err(|_| "Internal error: Iagon API version lock poisoned".to_string())? =
+            Some(negotiated.clone());
+    }
+
+    let matches_expected = reachable && negotiated == EXPECTED_API_VERSION;
+    let message = if matches_expected {
+        format!("Iagon API {EXPECTED_API_VERSION} is live.")
+    } else if reachable {
+        format!(
+            "Iagon reports API version {negotiated}, but this build targets {EXPECTED_API_VERSION}. Some features may not work correctly until the client is updated."
+        )
+    } else {
+        format!(
+            "Iagon's {EXPECTED_API_VERSION} endpoints returned 404. The API may have changed — check for a client update."
+        )
+    };
+
+    Ok(IagonApiVersionCheck {
+        expected: EXPECTED_API_VERSION.to_string(),
+        negotiated,
+        matches_expected,
+        message,
+    })
 }
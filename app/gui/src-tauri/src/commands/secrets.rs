@@ -1,8 +1,11 @@
+use crate::commands::wallet::WalletState;
+use crate::crypto::derivation::{bid_b_info, bip39_seed, derive_scalar, seller_a_info, seller_r_info};
 use crate::crypto::secrets::{
-    decrypt_secret, encrypt_secret, secure_delete, EncryptedSecret, SecretsKey,
+    decrypt_secret, decrypt_with_passphrase, encrypt_secret_v2, encrypt_with_passphrase,
+    secure_delete, zeroize_bytes, EncryptedSecret, EncryptedVault, NonceSequence, SecretsKey,
 };
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Managed state holding the base directory for secret storage.
 pub struct SecretsDir(pub PathBuf);
@@ -10,18 +13,23 @@ pub struct SecretsDir(pub PathBuf);
 // ── Helpers ─────────────────────────────────────────────────────────────
 
 fn get_secrets_key(key_state: &SecretsKey) -> Result<[u8; 32], String> {
-    let guard = key_state
-        .0
-        .lock()
-        .map_err(|_| "Internal error: secrets key lock poisoned".to_string())?;
-    match *guard {
-        Some(key) => Ok(key),
-        None => Err("Wallet is locked — unlock to access secrets".to_string()),
-    }
+    key_state.get()
 }
 
-fn encrypt_and_write(key: &[u8; 32], path: &std::path::Path, data: &[u8]) -> Result<(), String> {
-    let encrypted = encrypt_secret(key, data)?;
+/// Filename (relative to a `SecretsDir` base) of the nonce counter every
+/// v2-encrypting call site in this vault shares — the same counter
+/// `rotate_secrets_key` persists to, so no two secrets under the same key
+/// ever draw the same nonce no matter which command wrote them.
+const NONCE_COUNTER_FILENAME: &str = "nonce_counter";
+
+fn encrypt_and_write(
+    key: &[u8; 32],
+    base: &Path,
+    path: &std::path::Path,
+    data: &[u8],
+) -> Result<(), String> {
+    let nonce_seq = NonceSequence::new(base.join(NONCE_COUNTER_FILENAME));
+    let encrypted = encrypt_secret_v2(key, data, &nonce_seq)?;
     let json = serde_json::to_string_pretty(&encrypted)
         .map_err(|e| format!("Failed to serialize encrypted secret: {e}"))?;
     std::fs::write(path, json).map_err(|e| format!("Failed to write secret: {e}"))?;
@@ -36,11 +44,24 @@ fn read_and_decrypt(key: &[u8; 32], path: &std::path::Path) -> Result<Vec<u8>, S
     decrypt_secret(key, &encrypted)
 }
 
-fn chrono_now() -> String {
-    let dur = std::time::SystemTime::now()
+fn get_unlocked_mnemonic(wallet_state: &WalletState) -> Result<String, String> {
+    wallet_state
+        .mnemonic
+        .lock()
+        .map_err(|_| "Internal error: wallet state lock poisoned".to_string())?
+        .clone()
+        .ok_or_else(|| "Wallet is locked — unlock to derive secrets".to_string())
+}
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default();
-    format!("{}", dur.as_secs())
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn chrono_now() -> String {
+    now_secs().to_string()
 }
 
 // ── Seller secrets ──────────────────────────────────────────────────────
@@ -86,11 +107,51 @@ pub fn store_seller_secrets(
         serde_json::to_string(&file).map_err(|e| format!("Failed to serialize: {e}"))?;
     encrypt_and_write(
         &key,
+        &state.0,
         &dir.join(format!("{token_name}.json")),
         plaintext.as_bytes(),
     )
 }
 
+/// Regenerate a seller's `a`/`r` scalars from the wallet mnemonic instead of
+/// the frontend, and persist them in the same format `store_seller_secrets`
+/// would. Because derivation is keyed only by `token_name`, this lets a user
+/// who lost their secrets directory recover it from the mnemonic alone —
+/// the same mnemonic + token name always yields the same scalars.
+#[tauri::command]
+pub fn derive_seller_secrets(
+    state: tauri::State<'_, SecretsDir>,
+    key_state: tauri::State<'_, SecretsKey>,
+    wallet_state: tauri::State<'_, WalletState>,
+    token_name: String,
+) -> Result<SellerSecretResult, String> {
+    let key = get_secrets_key(&key_state)?;
+    let mnemonic = get_unlocked_mnemonic(&wallet_state)?;
+    let seed = bip39_seed(&mnemonic);
+    let a = derive_scalar(&seed, &seller_a_info(&token_name));
+    let r = derive_scalar(&seed, &seller_r_info(&token_name));
+
+    let dir = seller_dir(&state.0);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create seller secrets dir: {e}"))?;
+    let file = SellerSecretFile {
+        token_name: token_name.clone(),
+        a: a.clone(),
+        r: r.clone(),
+        created_at: chrono_now(),
+    };
+    let plaintext =
+        serde_json::to_string(&file).map_err(|e| format!("Failed to serialize: {e}"))?;
+    encrypt_and_write(
+        &key,
+        &state.0,
+        &dir.join(format!("{token_name}.json")),
+        plaintext.as_bytes(),
+    )?;
+
+    Ok(SellerSecretResult { a, r })
+}
+
 #[tauri::command]
 pub fn get_seller_secrets(
     state: tauri::State<'_, SecretsDir>,
@@ -206,11 +267,52 @@ pub fn store_bid_secrets(
         serde_json::to_string(&file).map_err(|e| format!("Failed to serialize: {e}"))?;
     encrypt_and_write(
         &key,
+        &state.0,
         &dir.join(format!("{bid_token_name}.json")),
         plaintext.as_bytes(),
     )
 }
 
+/// Regenerate a bid's `b` scalar from the wallet mnemonic, same recovery
+/// story as `derive_seller_secrets`. Keyed by `bid_token_name` so the
+/// derived scalar matches what was originally generated for that bid.
+#[tauri::command]
+pub fn derive_bid_secrets(
+    state: tauri::State<'_, SecretsDir>,
+    key_state: tauri::State<'_, SecretsKey>,
+    wallet_state: tauri::State<'_, WalletState>,
+    bid_token_name: String,
+    encryption_token_name: String,
+) -> Result<BidSecretResult, String> {
+    let key = get_secrets_key(&key_state)?;
+    let mnemonic = get_unlocked_mnemonic(&wallet_state)?;
+    let seed = bip39_seed(&mnemonic);
+    let b = derive_scalar(&seed, &bid_b_info(&bid_token_name));
+
+    let dir = bid_dir(&state.0);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create bid secrets dir: {e}"))?;
+    let file = BidSecretFile {
+        bid_token_name: bid_token_name.clone(),
+        encryption_token_name: encryption_token_name.clone(),
+        b: b.clone(),
+        created_at: chrono_now(),
+    };
+    let plaintext =
+        serde_json::to_string(&file).map_err(|e| format!("Failed to serialize: {e}"))?;
+    encrypt_and_write(
+        &key,
+        &state.0,
+        &dir.join(format!("{bid_token_name}.json")),
+        plaintext.as_bytes(),
+    )?;
+
+    Ok(BidSecretResult {
+        b,
+        encryption_token_name,
+    })
+}
+
 #[tauri::command]
 pub fn get_bid_secrets(
     state: tauri::State<'_, SecretsDir>,
@@ -306,7 +408,7 @@ pub struct AcceptBidSecretResult {
     snark_tx_hash: String,
 }
 
-fn accept_bid_dir(base: &PathBuf) -> PathBuf {
+pub(crate) fn accept_bid_dir(base: &PathBuf) -> PathBuf {
     base.join("accept-bid")
 }
 
@@ -343,6 +445,7 @@ pub fn store_accept_bid_secrets(
         serde_json::to_string(&file).map_err(|e| format!("Failed to serialize: {e}"))?;
     encrypt_and_write(
         &key,
+        &state.0,
         &dir.join(format!("{encryption_token_name}.json")),
         plaintext.as_bytes(),
     )
@@ -392,3 +495,427 @@ pub fn has_accept_bid_secrets(
     let path = accept_bid_dir(&state.0).join(format!("{encryption_token_name}.json"));
     Ok(path.exists())
 }
+
+/// Delete every accept-bid secret whose `ttl` (plus `grace_period_secs`, so
+/// freshly expired entries survive a short window) has already elapsed,
+/// reclaiming the Groth16 witness material (`a0`, `r0`, `hk`) they hold.
+/// Returns the `encryption_token_name` of each entry removed.
+#[tauri::command]
+pub fn prune_expired_accept_bid_secrets(
+    state: tauri::State<'_, SecretsDir>,
+    key_state: tauri::State<'_, SecretsKey>,
+    grace_period_secs: Option<i64>,
+) -> Result<Vec<String>, String> {
+    let key = get_secrets_key(&key_state)?;
+    let dir = accept_bid_dir(&state.0);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let grace_period_secs = grace_period_secs.unwrap_or(0);
+    let now = now_secs();
+    let mut removed = Vec::new();
+
+    for entry in std::fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read accept-bid secrets dir: {e}"))?
+    {
+        let entry = entry.map_err(|e| format!("Failed to read dir entry: {e}"))?;
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+
+        let plaintext = read_and_decrypt(&key, &path)
+            .map_err(|e| format!("Failed to decrypt {}: {e}", path.display()))?;
+        let file: AcceptBidSecretFile = serde_json::from_slice(&plaintext)
+            .map_err(|e| format!("Invalid accept-bid secret {}: {e}", path.display()))?;
+
+        if file.ttl + grace_period_secs < now {
+            secure_delete(&path)?;
+            removed.push(file.encryption_token_name);
+        }
+    }
+
+    Ok(removed)
+}
+
+/// Sum of on-disk file sizes for accept-bid secrets that are past their
+/// `ttl` (plus `grace_period_secs`), for `get_disk_usage`'s reclaimable-space
+/// figure. Unlike `prune_expired_accept_bid_secrets`, a file that fails to
+/// decrypt or parse is skipped rather than aborting the scan — this is an
+/// estimate for display, not a destructive operation.
+pub(crate) fn expired_accept_bid_bytes(base: &PathBuf, key: &[u8; 32], grace_period_secs: i64) -> u64 {
+    let dir = accept_bid_dir(base);
+    let Ok(read_dir) = std::fs::read_dir(&dir) else {
+        return 0;
+    };
+    let now = now_secs();
+    let mut total = 0u64;
+
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+        let Ok(plaintext) = read_and_decrypt(key, &path) else {
+            continue;
+        };
+        let Ok(file) = serde_json::from_slice::<AcceptBidSecretFile>(&plaintext) else {
+            continue;
+        };
+        if file.ttl + grace_period_secs < now {
+            total += path.metadata().map(|m| m.len()).unwrap_or(0);
+        }
+    }
+
+    total
+}
+
+// ── Vault export/import ─────────────────────────────────────────────────
+//
+// A portable, passphrase-encrypted bundle of every seller/bid/accept-bid
+// secret file, for moving a vault to another machine. Each file is
+// decrypted with the live session key, collected with its category and
+// original filename, then the whole bundle is re-encrypted under a key
+// derived from the caller's passphrase — plaintext scalars never touch
+// disk or leave the process.
+
+#[derive(Serialize, Deserialize)]
+struct VaultEntry {
+    category: String,
+    filename: String,
+    plaintext: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct VaultBundle {
+    version: u32,
+    entries: Vec<VaultEntry>,
+}
+
+#[derive(Serialize)]
+pub struct VaultImportCounts {
+    seller: usize,
+    bid: usize,
+    #[serde(rename = "acceptBid")]
+    accept_bid: usize,
+    skipped: usize,
+}
+
+fn vault_categories(base: &PathBuf) -> [(&'static str, PathBuf); 3] {
+    [
+        ("seller", seller_dir(base)),
+        ("bid", bid_dir(base)),
+        ("accept-bid", accept_bid_dir(base)),
+    ]
+}
+
+/// Export every secret file into a single passphrase-encrypted bundle,
+/// returned base64-encoded (the convention this codebase uses for binary
+/// IPC payloads — see `commands::media::ImageResult`).
+#[tauri::command]
+pub fn export_vault(
+    state: tauri::State<'_, SecretsDir>,
+    key_state: tauri::State<'_, SecretsKey>,
+    passphrase: String,
+) -> Result<String, String> {
+    use base64::Engine;
+
+    let key = get_secrets_key(&key_state)?;
+    let mut entries = Vec::new();
+
+    for (category, dir) in vault_categories(&state.0) {
+        if !dir.exists() {
+            continue;
+        }
+        for entry in std::fs::read_dir(&dir)
+            .map_err(|e| format!("Failed to read {category} secrets dir: {e}"))?
+        {
+            let entry = entry.map_err(|e| format!("Failed to read dir entry: {e}"))?;
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                continue;
+            }
+            let filename = entry.file_name().to_string_lossy().to_string();
+            let plaintext = read_and_decrypt(&key, &path)?;
+            let plaintext = String::from_utf8(plaintext)
+                .map_err(|_| format!("Decrypted data for {filename} is not valid UTF-8"))?;
+            entries.push(VaultEntry {
+                category: category.to_string(),
+                filename,
+                plaintext,
+            });
+        }
+    }
+
+    let bundle = VaultBundle {
+        version: 1,
+        entries,
+    };
+    let json = serde_json::to_vec(&bundle).map_err(|e| format!("Failed to serialize vault: {e}"))?;
+    let encrypted = encrypt_with_passphrase(&json, &passphrase)?;
+    let encrypted_json = serde_json::to_vec(&encrypted)
+        .map_err(|e| format!("Failed to serialize encrypted vault: {e}"))?;
+
+    Ok(base64::engine::general_purpose::STANDARD.encode(&encrypted_json))
+}
+
+/// Import a bundle produced by `export_vault`, recreating the seller/bid/
+/// accept-bid directory layout. Existing files with the same name are
+/// skipped unless `overwrite` is set. Returns a per-category count of what
+/// was actually imported.
+#[tauri::command]
+pub fn import_vault(
+    state: tauri::State<'_, SecretsDir>,
+    key_state: tauri::State<'_, SecretsKey>,
+    passphrase: String,
+    bundle_base64: String,
+    overwrite: bool,
+) -> Result<VaultImportCounts, String> {
+    use base64::Engine;
+
+    let key = get_secrets_key(&key_state)?;
+    let encrypted_json = base64::engine::general_purpose::STANDARD
+        .decode(&bundle_base64)
+        .map_err(|e| format!("Invalid vault bundle encoding: {e}"))?;
+    let encrypted: EncryptedVault = serde_json::from_slice(&encrypted_json)
+        .map_err(|e| format!("Invalid vault bundle: {e}"))?;
+    let decrypted = decrypt_with_passphrase(&encrypted, &passphrase)?;
+    let bundle: VaultBundle =
+        serde_json::from_slice(&decrypted).map_err(|e| format!("Invalid vault contents: {e}"))?;
+
+    let mut counts = VaultImportCounts {
+        seller: 0,
+        bid: 0,
+        accept_bid: 0,
+        skipped: 0,
+    };
+
+    for entry in bundle.entries {
+        let dir = match entry.category.as_str() {
+            "seller" => seller_dir(&state.0),
+            "bid" => bid_dir(&state.0),
+            "accept-bid" => accept_bid_dir(&state.0),
+            other => return Err(format!("Unknown vault category: {other}")),
+        };
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {} secrets dir: {e}", entry.category))?;
+
+        let path = dir.join(&entry.filename);
+        if path.exists() && !overwrite {
+            counts.skipped += 1;
+            continue;
+        }
+
+        encrypt_and_write(&key, &state.0, &path, entry.plaintext.as_bytes())?;
+        match entry.category.as_str() {
+            "seller" => counts.seller += 1,
+            "bid" => counts.bid += 1,
+            "accept-bid" => counts.accept_bid += 1,
+            _ => {}
+        }
+    }
+
+    Ok(counts)
+}
+
+// ── Secrets key rotation ────────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct RotateKeyCounts {
+    seller: usize,
+    bid: usize,
+    #[serde(rename = "acceptBid")]
+    accept_bid: usize,
+}
+
+const ROTATION_JOURNAL_FILENAME: &str = "rotation_journal.json";
+
+/// Paths still needing re-encryption in an in-progress `rotate_secrets_key`
+/// run. Persisted after every single file finishes its rotation (not just
+/// at the end), so an interrupted run resumes with exactly the files not
+/// yet confirmed done — never redoing a completed file, never skipping one
+/// that was in flight when the process died.
+#[derive(Serialize, Deserialize)]
+struct RotationJournal {
+    pending: Vec<PathBuf>,
+}
+
+fn rotation_journal_path(base: &Path) -> PathBuf {
+    base.join(ROTATION_JOURNAL_FILENAME)
+}
+
+fn read_rotation_journal(base: &Path) -> Result<Option<RotationJournal>, String> {
+    let path = rotation_journal_path(base);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read rotation journal: {e}"))?;
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| format!("Invalid rotation journal: {e}"))
+}
+
+fn write_rotation_journal(base: &Path, journal: &RotationJournal) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(journal)
+        .map_err(|e| format!("Failed to serialize rotation journal: {e}"))?;
+    let path = rotation_journal_path(base);
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json)
+        .map_err(|e| format!("Failed to write rotation journal: {e}"))?;
+    std::fs::rename(&tmp_path, &path)
+        .map_err(|e| format!("Failed to commit rotation journal: {e}"))
+}
+
+fn clear_rotation_journal(base: &Path) -> Result<(), String> {
+    let path = rotation_journal_path(base);
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .map_err(|e| format!("Failed to clear rotation journal: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Re-encrypt every secret file under `seller/`, `bid/`, and `accept-bid/`
+/// from `old_key` to `new_key` (needed whenever the derived secrets key
+/// changes, e.g. the wallet's underlying mnemonic is replaced), bumping
+/// each record to the current v2 format (`encrypt_secret_v2`, persisted
+/// nonce counter) along the way. Every record is re-encrypted whatever its
+/// `EncryptedSecret.version`; the field is not read here.
+///
+/// Driven by an on-disk journal rather than an in-memory batch: the
+/// journal is written before any file is touched, and each file is popped
+/// off and the journal re-persisted the moment that file's own rotation
+/// (decrypt, re-encrypt to a `.tmp` sibling, `secure_delete` the original,
+/// rename the `.tmp` over it) is fully committed. A crash at any point
+/// leaves every already-rotated file on the new key and every
+/// not-yet-reached file untouched on the old one — calling this again
+/// resumes from the journal instead of re-scanning the directories or
+/// redoing completed work. Counts returned reflect only the files rotated
+/// in *this* call; resuming a previously interrupted rotation doesn't
+/// recount files a prior call already finished.
+///
+/// One case the journal alone can't distinguish: a crash between the final
+/// rename committing a file to `new_key` and the journal pop that would
+/// have recorded it as done leaves that path still listed pending, but
+/// already on the new key. Resuming tries `new_key` on a still-pending
+/// path before falling back to `old_key`, so that file is recognized as
+/// already finished instead of failing AEAD auth and aborting the whole
+/// rotation.
+///
+/// Not a `#[tauri::command]`: there's no real trigger for this yet (the
+/// wallet's secrets key is derived from the mnemonic, and nothing in this
+/// tree currently replaces a wallet's mnemonic in place — see
+/// `change_wallet_password`'s own doc comment), so there's no caller that
+/// can legitimately produce `old_key`/`new_key`. Exposing that over IPC
+/// would let any webview JS re-key the whole vault with attacker-supplied
+/// key material. This stays an internal helper, ready to be wired to a
+/// real mnemonic-replacement flow (and registered as a command) the day
+/// one exists — called here with both key buffers owned so it can zeroize
+/// them itself on every exit path rather than leaving that to a caller.
+pub(crate) fn rotate_secrets_key(
+    base: &Path,
+    mut old_key: [u8; 32],
+    mut new_key: [u8; 32],
+) -> Result<RotateKeyCounts, String> {
+    let result = rotate_secrets_key_inner(base, &old_key, &new_key);
+    zeroize_bytes(&mut old_key);
+    zeroize_bytes(&mut new_key);
+    result
+}
+
+fn rotate_secrets_key_inner(
+    base: &Path,
+    old_key: &[u8; 32],
+    new_key: &[u8; 32],
+) -> Result<RotateKeyCounts, String> {
+    let mut journal = match read_rotation_journal(base)? {
+        Some(journal) => journal,
+        None => {
+            let mut pending = Vec::new();
+            for (_, dir) in vault_categories(base) {
+                if !dir.exists() {
+                    continue;
+                }
+                for entry in std::fs::read_dir(&dir)
+                    .map_err(|e| format!("Failed to read secrets dir: {e}"))?
+                {
+                    let entry = entry.map_err(|e| format!("Failed to read dir entry: {e}"))?;
+                    let path = entry.path();
+                    if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                        pending.push(path);
+                    }
+                }
+            }
+            let journal = RotationJournal { pending };
+            write_rotation_journal(base, &journal)?;
+            journal
+        }
+    };
+
+    let mut counts = RotateKeyCounts {
+        seller: 0,
+        bid: 0,
+        accept_bid: 0,
+    };
+    let nonce_seq = NonceSequence::new(base.join(NONCE_COUNTER_FILENAME));
+
+    while let Some(path) = journal.pending.last().cloned() {
+        let tmp_path = path.with_extension("json.tmp");
+        let mut rotated_here = false;
+
+        if !path.exists() && tmp_path.exists() {
+            // A previous run got as far as deleting the original and
+            // writing the tmp sibling before dying — finish the commit
+            // rather than trying (and failing) to decrypt a file that's
+            // already gone.
+            std::fs::rename(&tmp_path, &path)
+                .map_err(|e| format!("Failed to resume committing {}: {e}", path.display()))?;
+            rotated_here = true;
+        } else if path.exists() && read_and_decrypt(new_key, &path).is_ok() {
+            // A previous run got all the way through this file — wrote the
+            // tmp sibling, deleted the original, renamed the tmp back into
+            // place — and only then died before popping it off the journal.
+            // It's already on new_key, so decrypting with it (rather than
+            // assuming old_key still applies just because the path exists)
+            // is what tells this case apart from an untouched file; nothing
+            // left to do but record it below.
+        } else if path.exists() {
+            let plaintext = read_and_decrypt(old_key, &path)
+                .map_err(|e| format!("Failed to decrypt {}: {e}", path.display()))?;
+            let encrypted = encrypt_secret_v2(new_key, &plaintext, &nonce_seq)?;
+            let json = serde_json::to_string_pretty(&encrypted)
+                .map_err(|e| format!("Failed to serialize re-encrypted secret: {e}"))?;
+            std::fs::write(&tmp_path, json)
+                .map_err(|e| format!("Failed to write re-encrypted {}: {e}", path.display()))?;
+            secure_delete(&path)
+                .map_err(|e| format!("Failed to delete old {}: {e}", path.display()))?;
+            std::fs::rename(&tmp_path, &path)
+                .map_err(|e| format!("Failed to commit rotated {}: {e}", path.display()))?;
+            rotated_here = true;
+        }
+        // Neither the original nor a tmp sibling exists: an earlier run
+        // already finished this file and recorded it in the journal it was
+        // about to persist when it crashed — nothing left to do.
+
+        if let Some(category) = path
+            .parent()
+            .filter(|_| rotated_here)
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+        {
+            match category {
+                "seller" => counts.seller += 1,
+                "bid" => counts.bid += 1,
+                "accept-bid" => counts.accept_bid += 1,
+                _ => {}
+            }
+        }
+
+        journal.pending.pop();
+        write_rotation_journal(base, &journal)?;
+    }
+
+    clear_rotation_journal(base)?;
+    Ok(counts)
+}
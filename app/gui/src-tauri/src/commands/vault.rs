@@ -0,0 +1,55 @@
+use crate::crypto::kv_store::EncryptedStore;
+use crate::crypto::secrets::SecretsKey;
+
+fn get_secrets_key(key_state: &SecretsKey) -> Result<[u8; 32], String> {
+    key_state.get()
+}
+
+/// Store `plaintext` under `key` in the general-purpose encrypted vault.
+/// Unlike the seller/bid/accept-bid stores, `key` is an arbitrary
+/// caller-chosen label rather than a fixed scalar role — this is the
+/// catch-all vault for anything that doesn't fit those purpose-built ones.
+#[tauri::command]
+pub fn vault_put(
+    store: tauri::State<'_, EncryptedStore>,
+    key_state: tauri::State<'_, SecretsKey>,
+    key: String,
+    plaintext: Vec<u8>,
+) -> Result<(), String> {
+    let secrets_key = get_secrets_key(&key_state)?;
+    store.put(&secrets_key, &key, &plaintext)
+}
+
+/// Fetch the decrypted value stored under `key`, or `None` if nothing's
+/// been stored there.
+#[tauri::command]
+pub fn vault_get(
+    store: tauri::State<'_, EncryptedStore>,
+    key_state: tauri::State<'_, SecretsKey>,
+    key: String,
+) -> Result<Option<Vec<u8>>, String> {
+    let secrets_key = get_secrets_key(&key_state)?;
+    store.get(&secrets_key, &key)
+}
+
+/// Securely delete the value stored under `key`. A no-op if nothing was
+/// ever stored there.
+#[tauri::command]
+pub fn vault_remove(
+    store: tauri::State<'_, EncryptedStore>,
+    key_state: tauri::State<'_, SecretsKey>,
+    key: String,
+) -> Result<(), String> {
+    let secrets_key = get_secrets_key(&key_state)?;
+    store.remove(&secrets_key, &key)
+}
+
+/// List every logical key currently stored in the vault.
+#[tauri::command]
+pub fn vault_list_keys(
+    store: tauri::State<'_, EncryptedStore>,
+    key_state: tauri::State<'_, SecretsKey>,
+) -> Result<Vec<String>, String> {
+    let secrets_key = get_secrets_key(&key_state)?;
+    store.list_keys(&secrets_key)
+}
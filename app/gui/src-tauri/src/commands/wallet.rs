@@ -1,7 +1,25 @@
 use std::sync::Mutex;
 
-use crate::crypto::secrets::{derive_secrets_key, SecretsKey};
-use crate::crypto::wallet::{decrypt_mnemonic, encrypt_mnemonic, EncryptedWallet};
+use crate::crypto::ledger::{LedgerAddress, LedgerDevice, SECRETS_KEY_CHALLENGE};
+use crate::crypto::secrets::{
+    derive_secrets_key, derive_secrets_key_from_signature, zeroize_bytes, SecretsKey,
+};
+use crate::crypto::sharding::{combine_shares, split_secret, Share};
+use crate::crypto::wallet::{
+    change_password, decrypt_mnemonic, encrypt_ledger_wallet, encrypt_mnemonic,
+    migrate_if_outdated, EncryptedWallet,
+};
+
+/// Zeroize a mnemonic `String` in place, the same volatile-write-then-fence
+/// two-step `crypto::secrets::zeroize_bytes` uses for key material — a NUL
+/// byte is valid single-byte UTF-8, so overwriting every byte with 0 keeps
+/// the `String` invariant `as_bytes_mut` requires.
+fn zeroize_mnemonic(mnemonic: &mut String) {
+    for byte in unsafe { mnemonic.as_bytes_mut() } {
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+    std::sync::atomic::fence(std::sync::atomic::Ordering::SeqCst);
+}
 
 /// Application state for wallet management.
 pub struct WalletState {
@@ -47,9 +65,16 @@ pub fn create_wallet(
     Ok(())
 }
 
-/// Unlock the wallet by decrypting the mnemonic with the password.
-/// Returns the mnemonic words as a JSON array of strings.
-/// Also derives the secrets encryption key from the mnemonic.
+/// Unlock the wallet.
+///
+/// For a mnemonic wallet this decrypts the mnemonic with `password` and
+/// returns its words. For a Ledger wallet (`kind == "ledger"`) there's no
+/// password and no mnemonic: unlocking instead confirms the device is
+/// connected and has it sign `SECRETS_KEY_CHALLENGE`, deriving `SecretsKey`
+/// from that signature. `password` is ignored in that case and the
+/// returned word list is empty.
+///
+/// Either way, this also derives and stores the secrets encryption key.
 #[tauri::command]
 pub fn unlock_wallet(
     state: tauri::State<'_, WalletState>,
@@ -62,15 +87,30 @@ pub fn unlock_wallet(
     let encrypted: EncryptedWallet =
         serde_json::from_str(&json).map_err(|e| format!("Invalid wallet file format: {e}"))?;
 
+    if encrypted.kind == "ledger" {
+        let device = LedgerDevice::connect()?;
+        let signature = device.sign_challenge(SECRETS_KEY_CHALLENGE)?;
+        let secrets_key = derive_secrets_key_from_signature(&signature)?.into_key();
+        secrets_key_state.set(secrets_key)?;
+        return Ok(Vec::new());
+    }
+
     let mnemonic = decrypt_mnemonic(&encrypted, &password)?;
     let words: Vec<String> = mnemonic.split_whitespace().map(String::from).collect();
 
+    // Transparently re-encrypt under the current KDF defaults if this
+    // wallet file predates a parameter hardening — never strands older
+    // wallets on weaker parameters once they're unlocked.
+    if let Some(migrated) = migrate_if_outdated(&encrypted, &password)? {
+        let json = serde_json::to_string_pretty(&migrated)
+            .map_err(|e| format!("Failed to serialize: {e}"))?;
+        std::fs::write(&state.wallet_path, json)
+            .map_err(|e| format!("Failed to write wallet file: {e}"))?;
+    }
+
     // Derive the secrets encryption key from the mnemonic
-    let secrets_key = derive_secrets_key(&mnemonic)?;
-    *secrets_key_state
-        .0
-        .lock()
-        .map_err(|_| "Internal error: secrets key lock poisoned".to_string())? = Some(secrets_key);
+    let secrets_key = derive_secrets_key(&mnemonic)?.into_key();
+    secrets_key_state.set(secrets_key)?;
 
     *state
         .mnemonic
@@ -80,6 +120,59 @@ pub fn unlock_wallet(
     Ok(words)
 }
 
+/// Create a Ledger-backed wallet: connect to the device, read back its
+/// payment/stake address for account 0, and persist only that public
+/// material — never a seed — tagged `kind: "ledger"`.
+#[tauri::command]
+pub fn create_wallet_ledger(state: tauri::State<'_, WalletState>) -> Result<LedgerAddress, String> {
+    let device = LedgerDevice::connect()?;
+    let address = device.get_address(0)?;
+
+    let encrypted = encrypt_ledger_wallet(address.clone());
+    let json = serde_json::to_string_pretty(&encrypted)
+        .map_err(|e| format!("Failed to serialize: {e}"))?;
+
+    if let Some(parent) = state.wallet_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create data directory: {e}"))?;
+    }
+
+    std::fs::write(&state.wallet_path, json)
+        .map_err(|e| format!("Failed to write wallet file: {e}"))?;
+
+    Ok(address)
+}
+
+/// Whether the current wallet file is Ledger-backed (`kind == "ledger"`)
+/// rather than an encrypted mnemonic. `false` if no wallet file exists yet.
+#[tauri::command]
+pub fn wallet_is_hardware(state: tauri::State<'_, WalletState>) -> Result<bool, String> {
+    if !state.wallet_path.exists() {
+        return Ok(false);
+    }
+    let json = std::fs::read_to_string(&state.wallet_path)
+        .map_err(|e| format!("Failed to read wallet file: {e}"))?;
+    let encrypted: EncryptedWallet =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid wallet file format: {e}"))?;
+    Ok(encrypted.kind == "ledger")
+}
+
+/// Read the payment/stake address for `account_index` back off the
+/// connected Ledger device.
+#[tauri::command]
+pub fn ledger_get_address(account_index: u32) -> Result<LedgerAddress, String> {
+    let device = LedgerDevice::connect()?;
+    device.get_address(account_index)
+}
+
+/// Sign a transaction body (CBOR, hex-encoded) on the connected Ledger
+/// device, returning the witness signature hex-encoded.
+#[tauri::command]
+pub fn ledger_sign_tx(tx_cbor_hex: String) -> Result<String, String> {
+    let device = LedgerDevice::connect()?;
+    device.sign_tx(&tx_cbor_hex)
+}
+
 /// Lock the wallet by clearing the mnemonic and secrets key from memory.
 #[tauri::command]
 pub fn lock_wallet(
@@ -87,16 +180,7 @@ pub fn lock_wallet(
     secrets_key_state: tauri::State<'_, SecretsKey>,
 ) -> Result<(), String> {
     // Zero and clear the secrets encryption key
-    {
-        let mut guard = secrets_key_state
-            .0
-            .lock()
-            .map_err(|_| "Internal error: secrets key lock poisoned".to_string())?;
-        if let Some(ref mut key) = *guard {
-            key.fill(0);
-        }
-        *guard = None;
-    }
+    secrets_key_state.lock_now()?;
 
     *state
         .mnemonic
@@ -105,6 +189,27 @@ pub fn lock_wallet(
     Ok(())
 }
 
+/// Lock the wallet immediately — same effect as `lock_wallet`, exposed
+/// under the name the frontend's idle-timeout enforcement calls.
+#[tauri::command]
+pub fn lock_now(
+    state: tauri::State<'_, WalletState>,
+    secrets_key_state: tauri::State<'_, SecretsKey>,
+) -> Result<(), String> {
+    lock_wallet(state, secrets_key_state)
+}
+
+/// Configure the secrets key's idle-timeout auto-lock window. Takes effect
+/// immediately: if currently unlocked, the deadline is extended from now
+/// using the new timeout rather than waiting for the next access.
+#[tauri::command]
+pub fn set_auto_lock_timeout(
+    secrets_key_state: tauri::State<'_, SecretsKey>,
+    seconds: u64,
+) -> Result<(), String> {
+    secrets_key_state.set_idle_timeout(std::time::Duration::from_secs(seconds))
+}
+
 /// Delete the wallet file and clear in-memory state (mnemonic + secrets key).
 #[tauri::command]
 pub fn delete_wallet(
@@ -117,16 +222,7 @@ pub fn delete_wallet(
     }
 
     // Zero and clear the secrets encryption key
-    {
-        let mut guard = secrets_key_state
-            .0
-            .lock()
-            .map_err(|_| "Internal error: secrets key lock poisoned".to_string())?;
-        if let Some(ref mut key) = *guard {
-            key.fill(0);
-        }
-        *guard = None;
-    }
+    secrets_key_state.lock_now()?;
 
     *state
         .mnemonic
@@ -135,6 +231,64 @@ pub fn delete_wallet(
     Ok(())
 }
 
+/// Change the wallet's password, re-encrypting the mnemonic with a fresh
+/// salt/nonce under the current KDF defaults. The mnemonic — and so the
+/// wallet's funds — is untouched; only the password protecting it changes.
+///
+/// `SecretsKey` is derived from the mnemonic, not the password (see
+/// `derive_secrets_key`), so already-stored seller/bid secrets stay
+/// decryptable across a password change — confirmed here by re-deriving
+/// the key from both the old and rekeyed wallet and comparing before
+/// committing anything to disk. The file itself is replaced atomically
+/// (write `wallet.json.tmp`, then rename over `wallet.json`) so a crash
+/// mid-write can't corrupt the only copy.
+#[tauri::command]
+pub fn change_wallet_password(
+    state: tauri::State<'_, WalletState>,
+    old_password: String,
+    new_password: String,
+) -> Result<(), String> {
+    let json = std::fs::read_to_string(&state.wallet_path)
+        .map_err(|e| format!("Failed to read wallet file: {e}"))?;
+
+    let encrypted: EncryptedWallet =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid wallet file format: {e}"))?;
+
+    let mut old_mnemonic = decrypt_mnemonic(&encrypted, &old_password)?;
+    let mut old_secrets_key = derive_secrets_key(&old_mnemonic)?.into_key();
+    zeroize_mnemonic(&mut old_mnemonic);
+
+    let rekeyed = change_password(&encrypted, &old_password, &new_password)?;
+
+    // Sanity-check the rekeyed file decrypts to the same mnemonic (and so
+    // the same secrets key) before it ever touches disk.
+    let mut new_mnemonic = decrypt_mnemonic(&rekeyed, &new_password)?;
+    let mut new_secrets_key = derive_secrets_key(&new_mnemonic)?.into_key();
+    zeroize_mnemonic(&mut new_mnemonic);
+
+    // Both copies have served their purpose the moment they're compared —
+    // zeroize them before the early return on mismatch, not just on the
+    // success path below.
+    let keys_match = old_secrets_key == new_secrets_key;
+    zeroize_bytes(&mut old_secrets_key);
+    zeroize_bytes(&mut new_secrets_key);
+    if !keys_match {
+        return Err(
+            "Internal error: secrets key changed during password rotation".to_string(),
+        );
+    }
+
+    let json = serde_json::to_string_pretty(&rekeyed)
+        .map_err(|e| format!("Failed to serialize: {e}"))?;
+    let tmp_path = state.wallet_path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json)
+        .map_err(|e| format!("Failed to write wallet file: {e}"))?;
+    std::fs::rename(&tmp_path, &state.wallet_path)
+        .map_err(|e| format!("Failed to commit wallet file: {e}"))?;
+
+    Ok(())
+}
+
 /// Reveal the mnemonic by re-verifying the password.
 /// This re-decrypts from disk rather than using the in-memory copy,
 /// ensuring the password is correct before showing sensitive data.
@@ -154,3 +308,53 @@ pub fn reveal_mnemonic(
 
     Ok(words)
 }
+
+/// Split the wallet mnemonic into `shares` Shamir shares, any `threshold`
+/// of which reconstruct it, for social or offline backup (e.g. handed to
+/// trusted contacts or stored across separate physical locations). Like
+/// `reveal_mnemonic`, re-decrypts from disk against `password` rather than
+/// trusting the in-memory copy, so a share set can't be generated without
+/// proving the password first.
+#[tauri::command]
+pub fn split_mnemonic_recovery_shares(
+    state: tauri::State<'_, WalletState>,
+    password: String,
+    threshold: u8,
+    shares: u8,
+) -> Result<Vec<Share>, String> {
+    let json = std::fs::read_to_string(&state.wallet_path)
+        .map_err(|e| format!("Failed to read wallet file: {e}"))?;
+
+    let encrypted: EncryptedWallet =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid wallet file format: {e}"))?;
+
+    let mut mnemonic = decrypt_mnemonic(&encrypted, &password)?;
+    let result = split_secret(mnemonic.as_bytes(), threshold, shares);
+    zeroize_mnemonic(&mut mnemonic);
+    result
+}
+
+/// Reconstruct a mnemonic from `threshold` or more shares produced by
+/// `split_mnemonic_recovery_shares`. Pure reconstruction — doesn't touch
+/// `WalletState` or require an existing wallet file, since the whole point
+/// of a recovery share is restoring a wallet whose original device/secrets
+/// are gone.
+#[tauri::command]
+pub fn combine_mnemonic_recovery_shares(shares: Vec<Share>) -> Result<Vec<String>, String> {
+    let mut secret = combine_shares(&shares)?;
+    let result = String::from_utf8(secret.clone())
+        .map_err(|_| "Reconstructed secret is not a valid mnemonic".to_string())
+        .map(|mnemonic| mnemonic.split_whitespace().map(String::from).collect());
+    zeroize_bytes_vec(&mut secret);
+    result
+}
+
+/// Zeroize a `Vec<u8>` in place, the same volatile-write-then-fence
+/// discipline `zeroize_bytes`/`zeroize_mnemonic` use for other owned
+/// secret buffers.
+fn zeroize_bytes_vec(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+    std::sync::atomic::fence(std::sync::atomic::Ordering::SeqCst);
+}
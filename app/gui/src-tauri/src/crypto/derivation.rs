@@ -0,0 +1,161 @@
+use hkdf::Hkdf;
+use pbkdf2::pbkdf2_hmac;
+use sha2::{Sha256, Sha512};
+
+/// BLS12-381 scalar field order (Fr) — the field the `snark` sidecar's
+/// Groth16 circuit operates over, so every derived `a`/`r`/`b` scalar must
+/// be reduced into this range to be a valid circuit input.
+const SCALAR_FIELD_ORDER: [u8; 32] = [
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+
+/// Iteration count for BIP39 seed derivation, fixed by the BIP39 spec.
+const BIP39_PBKDF2_ITERATIONS: u32 = 2048;
+
+/// Derive the 64-byte BIP39 seed from a mnemonic phrase.
+///
+/// Standard BIP39 seed derivation with no passphrase: PBKDF2-HMAC-SHA512
+/// over the mnemonic, salted with the literal string "mnemonic".
+pub fn bip39_seed(mnemonic: &str) -> [u8; 64] {
+    let mut seed = [0u8; 64];
+    pbkdf2_hmac::<Sha512>(
+        mnemonic.as_bytes(),
+        b"mnemonic",
+        BIP39_PBKDF2_ITERATIONS,
+        &mut seed,
+    );
+    seed
+}
+
+/// Deterministically derive a protocol scalar from a BIP39 seed and a
+/// domain-separated info string, hex-encoded like every other scalar in
+/// this codebase.
+///
+/// This is brain-wallet-style recovery: the same seed + info string always
+/// yields the same scalar, so a user who only kept their mnemonic can
+/// regenerate a secret file that was lost, rather than losing the auction
+/// it belongs to.
+pub fn derive_scalar(seed: &[u8; 64], info: &str) -> String {
+    let hk = Hkdf::<Sha256>::new(None, seed);
+    let mut okm = [0u8; 32];
+    hk.expand(info.as_bytes(), &mut okm)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    to_hex(&reduce_mod_scalar_field(okm))
+}
+
+/// Domain-separation info strings, one per derived secret role. Binding the
+/// role and token name into the HKDF info means the same mnemonic derives a
+/// different scalar per contract/token, the same way an independently
+/// generated one would.
+pub fn seller_a_info(token_name: &str) -> String {
+    format!("peace/seller/a/{token_name}")
+}
+
+pub fn seller_r_info(token_name: &str) -> String {
+    format!("peace/seller/r/{token_name}")
+}
+
+pub fn bid_b_info(bid_token_name: &str) -> String {
+    format!("peace/bid/b/{bid_token_name}")
+}
+
+/// Reduce a 32-byte big-endian integer modulo `SCALAR_FIELD_ORDER` via
+/// bit-serial long division (double-and-reduce), rather than pulling in a
+/// bignum crate for a single modulus operation.
+fn reduce_mod_scalar_field(value: [u8; 32]) -> [u8; 32] {
+    let mut remainder = [0u8; 32];
+    for byte in value {
+        for bit_index in (0..8).rev() {
+            let bit = (byte >> bit_index) & 1;
+            shl1_or_in(&mut remainder, bit);
+            if be_cmp(&remainder, &SCALAR_FIELD_ORDER) != std::cmp::Ordering::Less {
+                be_sub_assign(&mut remainder, &SCALAR_FIELD_ORDER);
+            }
+        }
+    }
+    remainder
+}
+
+/// Shift a big-endian 256-bit integer left by one bit, ORing `bit` into the
+/// new least-significant bit. The bit shifted out of the most significant
+/// byte is discarded — safe here because `remainder` is always kept below
+/// `SCALAR_FIELD_ORDER`, which never sets the array's top bit.
+fn shl1_or_in(value: &mut [u8; 32], bit: u8) {
+    let mut carry = bit;
+    for byte in value.iter_mut().rev() {
+        let new_carry = *byte >> 7;
+        *byte = (*byte << 1) | carry;
+        carry = new_carry;
+    }
+}
+
+fn be_cmp(a: &[u8; 32], b: &[u8; 32]) -> std::cmp::Ordering {
+    a.iter().cmp(b.iter())
+}
+
+/// Subtract `b` from `a` in place, assuming `a >= b`.
+fn be_sub_assign(a: &mut [u8; 32], b: &[u8; 32]) {
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = a[i] as i16 - b[i] as i16 - borrow;
+        if diff < 0 {
+            a[i] = (diff + 256) as u8;
+            borrow = 1;
+        } else {
+            a[i] = diff as u8;
+            borrow = 0;
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bip39_seed_deterministic() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+        assert_eq!(bip39_seed(mnemonic), bip39_seed(mnemonic));
+    }
+
+    #[test]
+    fn derive_scalar_deterministic() {
+        let seed = bip39_seed("test mnemonic phrase for unit testing");
+        let info = seller_a_info("token123");
+        assert_eq!(derive_scalar(&seed, &info), derive_scalar(&seed, &info));
+    }
+
+    #[test]
+    fn derive_scalar_differs_by_info() {
+        let seed = bip39_seed("test mnemonic phrase for unit testing");
+        let a = derive_scalar(&seed, &seller_a_info("token123"));
+        let r = derive_scalar(&seed, &seller_r_info("token123"));
+        assert_ne!(a, r);
+    }
+
+    #[test]
+    fn derive_scalar_differs_by_token_name() {
+        let seed = bip39_seed("test mnemonic phrase for unit testing");
+        let a1 = derive_scalar(&seed, &seller_a_info("token123"));
+        let a2 = derive_scalar(&seed, &seller_a_info("token456"));
+        assert_ne!(a1, a2);
+    }
+
+    #[test]
+    fn derive_scalar_is_below_field_order() {
+        let seed = bip39_seed("another test mnemonic");
+        let hex = derive_scalar(&seed, &seller_a_info("token"));
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+        let mut arr = [0u8; 32];
+        arr.copy_from_slice(&bytes);
+        assert_eq!(be_cmp(&arr, &SCALAR_FIELD_ORDER), std::cmp::Ordering::Less);
+    }
+}
@@ -0,0 +1,314 @@
+use super::secrets::{decrypt_secret, encrypt_secret_v2, secure_delete, EncryptedSecret, NonceSequence};
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Domain separator for filename hashing, same role as `SECRETS_KEY_SALT`:
+/// not secret, just keeps this derivation from colliding with an unrelated
+/// one that happens to hash the same key string.
+const FILENAME_SALT: &[u8] = b"PEACE_KV_FILENAME_V1";
+
+/// Default decrypted-value cache size. Generous enough to hold every
+/// secret a single wallet session is likely to touch without re-deriving
+/// AES-GCM decryption on every `get`, small enough that a compromised
+/// memory snapshot doesn't trivially dump the whole vault.
+const DEFAULT_CACHE_CAPACITY: usize = 64;
+
+const INDEX_FILENAME: &str = "index.json";
+
+/// Filename (relative to `dir`) of this store's persisted nonce counter —
+/// scoped per-store rather than shared with `commands::secrets`'s, since an
+/// `EncryptedStore` lives under its own directory with its own lifetime.
+const NONCE_COUNTER_FILENAME: &str = "nonce_counter";
+
+/// Hash `key` into the on-disk filename its `EncryptedSecret` is stored
+/// under, so the logical key name itself never appears in the filesystem —
+/// only whoever holds the secrets key (and can decrypt the index) can map
+/// a filename back to the key it belongs to.
+fn filename_for_key(key: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(FILENAME_SALT);
+    hasher.update(key.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Write `data` to `path` atomically: write to a sibling temp file, then
+/// rename over the real path. A crash or power loss mid-write leaves the
+/// old file (or nothing, on first write) rather than a half-written one.
+fn atomic_write(path: &Path, data: &[u8]) -> Result<(), String> {
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, data)
+        .map_err(|e| format!("Failed to write {}: {e}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| format!("Failed to finalize {}: {e}", path.display()))
+}
+
+struct StoreState {
+    /// Decrypted values, keyed by the logical key (not the hashed
+    /// filename) — evicted LRU-first once `cache` hits capacity.
+    cache: LruCache<String, Vec<u8>>,
+}
+
+/// A keyed, encrypted secrets vault: `put`/`get`/`remove`/`list_keys`
+/// against a directory of `EncryptedSecret` files, one per logical key.
+///
+/// Every mutating operation (`put`, `remove`) is atomic — `put` writes then
+/// renames, `remove` renames the record aside before wiping it — so a crash
+/// mid-write never leaves a corrupt record behind. Values and
+/// the index are encrypted with `encrypt_secret_v2` against a nonce counter
+/// persisted alongside this store's own directory, since the same secrets
+/// key is reused across every entry for the store's whole lifetime.
+/// Decrypted values are cached in an LRU behind a `Mutex`, the same "guard
+/// the only mutable state behind one lock" discipline `SecretsKey` uses for
+/// its key material.
+pub struct EncryptedStore {
+    dir: PathBuf,
+    nonce_seq: NonceSequence,
+    state: Mutex<StoreState>,
+}
+
+impl EncryptedStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self::with_capacity(dir, DEFAULT_CACHE_CAPACITY)
+    }
+
+    pub fn with_capacity(dir: PathBuf, capacity: usize) -> Self {
+        let nonce_seq = NonceSequence::new(dir.join(NONCE_COUNTER_FILENAME));
+        Self {
+            dir,
+            nonce_seq,
+            state: Mutex::new(StoreState {
+                cache: LruCache::new(
+                    NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::new(1).unwrap()),
+                ),
+            }),
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, StoreState>, String> {
+        self.state
+            .lock()
+            .map_err(|_| "Internal error: encrypted store lock poisoned".to_string())
+    }
+
+    fn value_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", filename_for_key(key)))
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.dir.join(INDEX_FILENAME)
+    }
+
+    /// Read and decrypt the index of logical key names. An absent index
+    /// (nothing stored yet) is an empty set, not an error.
+    fn read_index(&self, secrets_key: &[u8; 32]) -> Result<HashSet<String>, String> {
+        let path = self.index_path();
+        if !path.exists() {
+            return Ok(HashSet::new());
+        }
+        let json = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read key index: {e}"))?;
+        let encrypted: EncryptedSecret =
+            serde_json::from_str(&json).map_err(|e| format!("Invalid key index: {e}"))?;
+        let plaintext = decrypt_secret(secrets_key, &encrypted)?;
+        serde_json::from_slice(&plaintext).map_err(|e| format!("Invalid key index contents: {e}"))
+    }
+
+    fn write_index(&self, secrets_key: &[u8; 32], keys: &HashSet<String>) -> Result<(), String> {
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create encrypted store dir: {e}"))?;
+        let plaintext =
+            serde_json::to_vec(keys).map_err(|e| format!("Failed to serialize key index: {e}"))?;
+        let encrypted = encrypt_secret_v2(secrets_key, &plaintext, &self.nonce_seq)?;
+        let json = serde_json::to_string_pretty(&encrypted)
+            .map_err(|e| format!("Failed to serialize key index: {e}"))?;
+        atomic_write(&self.index_path(), json.as_bytes())
+    }
+
+    /// Store `plaintext` under `key`, encrypting it and recording `key` in
+    /// the (also encrypted) index so `list_keys` can enumerate it later.
+    pub fn put(
+        &self,
+        secrets_key: &[u8; 32],
+        key: &str,
+        plaintext: &[u8],
+    ) -> Result<(), String> {
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create encrypted store dir: {e}"))?;
+
+        let encrypted = encrypt_secret_v2(secrets_key, plaintext, &self.nonce_seq)?;
+        let json = serde_json::to_string_pretty(&encrypted)
+            .map_err(|e| format!("Failed to serialize encrypted value: {e}"))?;
+        atomic_write(&self.value_path(key), json.as_bytes())?;
+
+        let mut keys = self.read_index(secrets_key)?;
+        if keys.insert(key.to_string()) {
+            self.write_index(secrets_key, &keys)?;
+        }
+
+        self.lock()?.cache.put(key.to_string(), plaintext.to_vec());
+        Ok(())
+    }
+
+    /// Fetch the decrypted value for `key`, serving from the LRU cache
+    /// when present rather than re-decrypting from disk.
+    pub fn get(&self, secrets_key: &[u8; 32], key: &str) -> Result<Option<Vec<u8>>, String> {
+        if let Some(cached) = self.lock()?.cache.get(key) {
+            return Ok(Some(cached.clone()));
+        }
+
+        let path = self.value_path(key);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json =
+            std::fs::read_to_string(&path).map_err(|e| format!("Failed to read value: {e}"))?;
+        let encrypted: EncryptedSecret =
+            serde_json::from_str(&json).map_err(|e| format!("Invalid encrypted value: {e}"))?;
+        let plaintext = decrypt_secret(secrets_key, &encrypted)?;
+
+        self.lock()?.cache.put(key.to_string(), plaintext.clone());
+        Ok(Some(plaintext))
+    }
+
+    /// Securely delete the value for `key` and drop it from the index and
+    /// cache. A no-op (not an error) if `key` was never stored.
+    pub fn remove(&self, secrets_key: &[u8; 32], key: &str) -> Result<(), String> {
+        let path = self.value_path(key);
+        let doomed = path.with_extension("json.del");
+        if path.exists() {
+            std::fs::rename(&path, &doomed)
+                .map_err(|e| format!("Failed to remove value: {e}"))?;
+        }
+        secure_delete(&doomed)?;
+
+        let mut keys = self.read_index(secrets_key)?;
+        if keys.remove(key) {
+            self.write_index(secrets_key, &keys)?;
+        }
+
+        self.lock()?.cache.pop(key);
+        Ok(())
+    }
+
+    /// List every logical key currently stored, decrypting the index to
+    /// recover the names — the on-disk filenames are hashes and can't be
+    /// reversed back into key strings without it.
+    pub fn list_keys(&self, secrets_key: &[u8; 32]) -> Result<Vec<String>, String> {
+        let mut keys: Vec<String> = self.read_index(secrets_key)?.into_iter().collect();
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key() -> [u8; 32] {
+        [7u8; 32]
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("peace_test_kv_store_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn put_get_roundtrip() {
+        let dir = test_dir("roundtrip");
+        let store = EncryptedStore::new(dir.clone());
+        let key = test_key();
+
+        store.put(&key, "wallet.seed", b"super secret bytes").unwrap();
+        let value = store.get(&key, "wallet.seed").unwrap();
+        assert_eq!(value, Some(b"super secret bytes".to_vec()));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn get_missing_key_is_none() {
+        let dir = test_dir("missing");
+        let store = EncryptedStore::new(dir.clone());
+        let key = test_key();
+
+        assert_eq!(store.get(&key, "never-stored").unwrap(), None);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn remove_deletes_value_and_index_entry() {
+        let dir = test_dir("remove");
+        let store = EncryptedStore::new(dir.clone());
+        let key = test_key();
+
+        store.put(&key, "a", b"one").unwrap();
+        store.put(&key, "b", b"two").unwrap();
+        store.remove(&key, "a").unwrap();
+
+        assert_eq!(store.get(&key, "a").unwrap(), None);
+        assert_eq!(store.list_keys(&key).unwrap(), vec!["b".to_string()]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn list_keys_reflects_puts() {
+        let dir = test_dir("list");
+        let store = EncryptedStore::new(dir.clone());
+        let key = test_key();
+
+        store.put(&key, "zeta", b"1").unwrap();
+        store.put(&key, "alpha", b"2").unwrap();
+
+        assert_eq!(
+            store.list_keys(&key).unwrap(),
+            vec!["alpha".to_string(), "zeta".to_string()]
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn filenames_do_not_contain_the_key_name() {
+        let dir = test_dir("filenames");
+        let store = EncryptedStore::new(dir.clone());
+        let key = test_key();
+
+        store.put(&key, "my-secret-label", b"data").unwrap();
+
+        let entries: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        assert!(entries.iter().all(|name| !name.contains("my-secret-label")));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn cache_serves_without_rereading_disk() {
+        let dir = test_dir("cache");
+        let store = EncryptedStore::new(dir.clone());
+        let key = test_key();
+
+        store.put(&key, "cached", b"value").unwrap();
+        // Corrupt the on-disk file directly — a cache hit shouldn't care.
+        std::fs::write(store.value_path("cached"), b"not valid json").unwrap();
+
+        assert_eq!(store.get(&key, "cached").unwrap(), Some(b"value".to_vec()));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
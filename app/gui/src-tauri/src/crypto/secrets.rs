@@ -1,19 +1,165 @@
 use aes_gcm::aead::Aead;
 use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
 use argon2::{Algorithm, Argon2, Params, Version};
+use fd_lock::RwLock as FileRwLock;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
 use std::io::Write;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default idle timeout before the secrets key auto-locks: 15 minutes of no
+/// successful `get()` calls. Overridable at runtime via `set_idle_timeout`.
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+struct SecretsKeyState {
+    key: Option<[u8; 32]>,
+    /// Instant past which `key` is considered expired. `None` alongside a
+    /// present `key` would mean "unlocked, never expires" — not currently
+    /// reachable, but `set` always stamps a deadline so this stays simple.
+    deadline: Option<Instant>,
+    idle_timeout: Duration,
+}
 
 /// In-memory secrets encryption key, derived from mnemonic on wallet unlock.
-/// Cleared on wallet lock.
-pub struct SecretsKey(pub Mutex<Option<[u8; 32]>>);
+///
+/// Auto-locks after `idle_timeout` of inactivity, the same "time-bounded
+/// unlock" pattern an exchange or bank session uses: every successful
+/// `get()` slides the deadline forward, and a `get()` past the deadline (or
+/// an explicit `lock_now()`) zeroizes the key bytes in place — with
+/// `secure_delete`'s "overwrite, don't just drop" rationale applied to
+/// memory instead of disk — rather than merely dropping the `Option`.
+pub struct SecretsKey(Mutex<SecretsKeyState>);
+
+impl SecretsKey {
+    pub fn new() -> Self {
+        Self(Mutex::new(SecretsKeyState {
+            key: None,
+            deadline: None,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+        }))
+    }
+
+    /// Unlock with a freshly-derived key, starting a new idle deadline.
+    pub fn set(&self, key: [u8; 32]) -> Result<(), String> {
+        let mut state = self.lock()?;
+        let timeout = state.idle_timeout;
+        state.key = Some(key);
+        state.deadline = Some(Instant::now() + timeout);
+        Ok(())
+    }
+
+    /// Fetch the key if unlocked and not expired, sliding the deadline
+    /// forward on success. Zeroizes and locks in place if the deadline has
+    /// passed.
+    pub fn get(&self) -> Result<[u8; 32], String> {
+        let mut state = self.lock()?;
+
+        let expired = match (state.key, state.deadline) {
+            (Some(_), Some(deadline)) => Instant::now() >= deadline,
+            (Some(_), None) => false,
+            (None, _) => return Err("Wallet is locked — unlock to access secrets".to_string()),
+        };
+
+        if expired {
+            Self::wipe(&mut state);
+            return Err("Wallet is locked — unlock to access secrets".to_string());
+        }
+
+        let timeout = state.idle_timeout;
+        state.deadline = Some(Instant::now() + timeout);
+        Ok(state.key.expect("checked Some above"))
+    }
+
+    /// Zeroize and lock immediately, regardless of the deadline.
+    pub fn lock_now(&self) -> Result<(), String> {
+        let mut state = self.lock()?;
+        Self::wipe(&mut state);
+        Ok(())
+    }
+
+    /// Change the idle timeout. Takes effect immediately: if currently
+    /// unlocked, the deadline is recomputed from now with the new timeout
+    /// rather than waiting for the next access.
+    pub fn set_idle_timeout(&self, timeout: Duration) -> Result<(), String> {
+        let mut state = self.lock()?;
+        state.idle_timeout = timeout;
+        if state.key.is_some() {
+            state.deadline = Some(Instant::now() + timeout);
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, SecretsKeyState>, String> {
+        self.0
+            .lock()
+            .map_err(|_| "Internal error: secrets key lock poisoned".to_string())
+    }
+
+    /// Overwrite the key bytes with zeros in place before dropping them, so
+    /// they don't linger in the mutex-guarded buffer.
+    fn wipe(state: &mut SecretsKeyState) {
+        if let Some(ref mut key) = state.key {
+            zeroize_bytes(key);
+        }
+        state.key = None;
+        state.deadline = None;
+    }
+}
+
+/// Overwrite `bytes` with zeros via a volatile write, then fence so the
+/// compiler can't treat it as a dead store and elide it — a plain
+/// `bytes.fill(0)` right before a value goes out of scope has nothing left
+/// to read the zeroed contents afterward, so LLVM is free to optimize it
+/// away entirely. This is what the `zeroize` crate does internally; we
+/// reimplement the same two-step here rather than depend on it.
+pub(crate) fn zeroize_bytes(bytes: &mut [u8; 32]) {
+    for byte in bytes.iter_mut() {
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+    std::sync::atomic::fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+/// Guards a freshly-derived key's bytes, zeroizing them on drop — including
+/// on an early-return path out of whatever function called
+/// `derive_secrets_key`/`derive_secrets_key_from_signature` before ever
+/// calling `into_key()`, not just the call sites that remember to clean up
+/// explicitly.
+pub struct DerivedKey([u8; 32]);
+
+impl DerivedKey {
+    /// Take the key bytes out for use (e.g. `SecretsKey::set`), zeroizing
+    /// this guard's own copy immediately so only the one copy handed back
+    /// here remains live afterward.
+    pub fn into_key(mut self) -> [u8; 32] {
+        let key = self.0;
+        zeroize_bytes(&mut self.0);
+        key
+    }
+}
+
+impl Drop for DerivedKey {
+    fn drop(&mut self) {
+        zeroize_bytes(&mut self.0);
+    }
+}
+
+impl Default for SecretsKey {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// Encrypted secret file format (JSON-serialized to disk).
 #[derive(Serialize, Deserialize)]
 pub struct EncryptedSecret {
-    /// Format version for future migrations.
+    /// Format version: 1 for a randomly drawn nonce (`encrypt_secret`), 2
+    /// for a nonce drawn from a persisted `NonceSequence` counter
+    /// (`encrypt_secret_v2`). `decrypt_secret` doesn't need to branch on
+    /// this — the nonce is always read straight from `nonce` regardless of
+    /// how it was generated — but it's what a future re-encryption pass
+    /// reads to decide whether a record still needs upgrading.
     pub version: u32,
     /// AES-256-GCM nonce (12 bytes, hex-encoded).
     pub nonce: String,
@@ -29,14 +175,35 @@ const SECRETS_KEY_SALT: &[u8; 16] = b"PEACE_SECRETS_V1";
 ///
 /// Uses Argon2id with light parameters (4 MiB, 1 iteration) since the
 /// mnemonic already has 256 bits of entropy.
-pub fn derive_secrets_key(mnemonic: &str) -> Result<[u8; 32], String> {
+pub fn derive_secrets_key(mnemonic: &str) -> Result<DerivedKey, String> {
     let params = Params::new(4096, 1, 1, Some(32)).map_err(|e| format!("Argon2 params: {e}"))?;
     let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
     let mut key = [0u8; 32];
-    argon2
-        .hash_password_into(mnemonic.as_bytes(), SECRETS_KEY_SALT, &mut key)
-        .map_err(|e| format!("Secrets key derivation failed: {e}"))?;
-    Ok(key)
+    if let Err(e) = argon2.hash_password_into(mnemonic.as_bytes(), SECRETS_KEY_SALT, &mut key) {
+        zeroize_bytes(&mut key);
+        return Err(format!("Secrets key derivation failed: {e}"));
+    }
+    Ok(DerivedKey(key))
+}
+
+/// Domain-separated from `SECRETS_KEY_SALT` so a Ledger-mode key can never
+/// collide with a mnemonic-mode key derived from the same bytes.
+const LEDGER_SECRETS_KEY_SALT: &[u8; 16] = b"PEACE_SECRETS_LG";
+
+/// Derive a 32-byte AES key from a Ledger device's signature over the fixed
+/// `crypto::ledger::SECRETS_KEY_CHALLENGE`, for wallets in Ledger mode where
+/// `derive_secrets_key`'s mnemonic is never available. Same light Argon2id
+/// parameters as `derive_secrets_key` — the signature already carries as
+/// much entropy as a mnemonic would.
+pub fn derive_secrets_key_from_signature(signature: &[u8]) -> Result<DerivedKey, String> {
+    let params = Params::new(4096, 1, 1, Some(32)).map_err(|e| format!("Argon2 params: {e}"))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+    let mut key = [0u8; 32];
+    if let Err(e) = argon2.hash_password_into(signature, LEDGER_SECRETS_KEY_SALT, &mut key) {
+        zeroize_bytes(&mut key);
+        return Err(format!("Secrets key derivation failed: {e}"));
+    }
+    Ok(DerivedKey(key))
 }
 
 /// Encrypt plaintext bytes with AES-256-GCM using the secrets key.
@@ -75,6 +242,213 @@ pub fn decrypt_secret(key: &[u8; 32], encrypted: &EncryptedSecret) -> Result<Vec
         .map_err(|_| "Failed to decrypt secret (wallet key may have changed)".to_string())
 }
 
+/// A persisted, monotonically increasing 96-bit nonce counter.
+///
+/// `encrypt_secret`'s random nonce hits the AES-GCM birthday bound at
+/// around 2^32 encryptions under one key — fine for a handful of secret
+/// files, not fine for a key that's reused across a long-lived vault. A
+/// counter that's durable across restarts instead guarantees every nonce
+/// under a given key is used exactly once, no matter how many times the
+/// process has been killed and relaunched in between — but only if
+/// `next()` itself can't be raced: Tauri dispatches command invocations
+/// concurrently, and several commands now share one `NonceSequence` over
+/// the same file, each constructing its own instance rather than going
+/// through a single shared one. `next()` takes an OS file lock (the same
+/// `fd_lock` advisory lock `process::instance_lock` uses) around the
+/// read-then-persist pair specifically so two racing callers — whether
+/// different threads in this process or, in principle, a second process
+/// pointed at the same counter file — can't both read the same value and
+/// hand out the same nonce.
+///
+/// The counter only uses the low 8 of its 12 bytes (big-endian, top 4
+/// bytes always zero) — `u64` is already far beyond any plausible
+/// encryption count for a single secret, and storing it as a fixed-width
+/// integer keeps the persistence format trivial.
+pub struct NonceSequence {
+    path: std::path::PathBuf,
+}
+
+impl NonceSequence {
+    pub fn new(path: std::path::PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Path of the advisory lock file guarding `next()`'s read-persist
+    /// pair — a sibling of the counter file itself rather than the counter
+    /// file, so locking never interferes with the counter's own
+    /// write-temp-fsync-rename dance.
+    fn lock_path(&self) -> std::path::PathBuf {
+        self.path.with_extension("lock")
+    }
+
+    fn read(&self) -> Result<u64, String> {
+        if !self.path.exists() {
+            return Ok(0);
+        }
+        let bytes = std::fs::read(&self.path)
+            .map_err(|e| format!("Failed to read nonce counter: {e}"))?;
+        let bytes: [u8; 8] = bytes
+            .try_into()
+            .map_err(|_| "Invalid nonce counter file: expected 8 bytes".to_string())?;
+        Ok(u64::from_be_bytes(bytes))
+    }
+
+    /// Persist `value` durably: write to a temp file, fsync it, then
+    /// rename over the real path. Fsyncing before the rename (rather than
+    /// after) means the rename is never observed without the data it
+    /// points at having actually reached disk.
+    fn persist(&self, value: u64) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create nonce counter dir: {e}"))?;
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        let mut file = std::fs::File::create(&tmp_path)
+            .map_err(|e| format!("Failed to write nonce counter: {e}"))?;
+        file.write_all(&value.to_be_bytes())
+            .map_err(|e| format!("Failed to write nonce counter: {e}"))?;
+        file.sync_all()
+            .map_err(|e| format!("Failed to fsync nonce counter: {e}"))?;
+        std::fs::rename(&tmp_path, &self.path)
+            .map_err(|e| format!("Failed to finalize nonce counter: {e}"))
+    }
+
+    /// Advance the counter and return the pre-increment value as a 12-byte
+    /// big-endian nonce. Fails closed: if the advanced counter can't be
+    /// persisted, no nonce is returned — the caller must not fall back to
+    /// encrypting with a nonce that was never durably claimed, since a
+    /// crash right after could hand the same value out again next run.
+    ///
+    /// Holds an exclusive OS file lock across the whole read-then-persist
+    /// pair so two callers racing this can't both read the same `current`
+    /// and both get handed the same nonce back — the one correctness
+    /// property a "nonce-misuse-resistant" counter can't skip.
+    fn next(&self) -> Result<[u8; 12], String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create nonce counter dir: {e}"))?;
+        }
+        let lock_file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(self.lock_path())
+            .map_err(|e| format!("Failed to open nonce counter lock: {e}"))?;
+        let mut file_lock = FileRwLock::new(lock_file);
+        let _guard = file_lock
+            .write()
+            .map_err(|e| format!("Failed to lock nonce counter: {e}"))?;
+
+        let current = self.read()?;
+        let next = current
+            .checked_add(1)
+            .ok_or_else(|| "Nonce counter exhausted".to_string())?;
+        self.persist(next)?;
+
+        let mut nonce = [0u8; 12];
+        nonce[4..].copy_from_slice(&current.to_be_bytes());
+        Ok(nonce)
+    }
+}
+
+/// Encrypt plaintext bytes with AES-256-GCM using a nonce drawn from
+/// `nonce_seq` instead of the RNG — nonce-misuse-resistant as long as
+/// `nonce_seq` is persisted somewhere that survives process restarts and
+/// is never shared between two different keys. `decrypt_secret` reads the
+/// result exactly like a v1 record; only how the nonce was produced
+/// differs.
+pub fn encrypt_secret_v2(
+    key: &[u8; 32],
+    plaintext: &[u8],
+    nonce_seq: &NonceSequence,
+) -> Result<EncryptedSecret, String> {
+    let nonce_bytes = nonce_seq.next()?;
+
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| format!("Cipher init: {e}"))?;
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|e| format!("Secret encryption failed: {e}"))?;
+
+    Ok(EncryptedSecret {
+        version: 2,
+        nonce: to_hex(&nonce_bytes),
+        ciphertext: to_hex(&ciphertext),
+    })
+}
+
+/// Passphrase-encrypted bundle format for vault export/import. Like
+/// `EncryptedSecret`, but carries its own embedded random salt rather than
+/// the fixed `SECRETS_KEY_SALT` — a user-chosen export passphrase, unlike
+/// the wallet mnemonic, doesn't already have enough entropy to skip one.
+#[derive(Serialize, Deserialize)]
+pub struct EncryptedVault {
+    pub version: u32,
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// Derive a 32-byte AES key from an export/import passphrase + salt. Same
+/// Argon2id parameters as `crypto::wallet`'s key derivation — both protect
+/// a user-chosen password rather than an already-high-entropy mnemonic.
+fn derive_passphrase_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+    let params = Params::new(65536, 3, 4, Some(32)).map_err(|e| format!("Argon2 params: {e}"))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+    let mut key = [0u8; 32];
+    argon2
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Passphrase key derivation failed: {e}"))?;
+    Ok(key)
+}
+
+/// Encrypt arbitrary bytes under a passphrase, embedding a fresh random
+/// salt so the caller doesn't have to manage one.
+pub fn encrypt_with_passphrase(
+    plaintext: &[u8],
+    passphrase: &str,
+) -> Result<EncryptedVault, String> {
+    let mut salt = [0u8; 16];
+    let mut nonce_bytes = [0u8; 12];
+    rand::rngs::OsRng.fill_bytes(&mut salt);
+    rand::rngs::OsRng.fill_bytes(&mut nonce_bytes);
+
+    let key = derive_passphrase_key(passphrase, &salt)?;
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| format!("Cipher init: {e}"))?;
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|e| format!("Vault encryption failed: {e}"))?;
+
+    Ok(EncryptedVault {
+        version: 1,
+        salt: to_hex(&salt),
+        nonce: to_hex(&nonce_bytes),
+        ciphertext: to_hex(&ciphertext),
+    })
+}
+
+/// Decrypt an `EncryptedVault` bundle with the export/import passphrase.
+pub fn decrypt_with_passphrase(vault: &EncryptedVault, passphrase: &str) -> Result<Vec<u8>, String> {
+    let salt = from_hex(&vault.salt)?;
+    let nonce_bytes = from_hex(&vault.nonce)?;
+    let ciphertext = from_hex(&vault.ciphertext)?;
+
+    if nonce_bytes.len() != 12 {
+        return Err("Invalid vault file: bad nonce length".to_string());
+    }
+
+    let key = derive_passphrase_key(passphrase, &salt)?;
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| format!("Cipher init: {e}"))?;
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    cipher
+        .decrypt(nonce, ciphertext.as_ref())
+        .map_err(|_| "Failed to decrypt vault (wrong passphrase?)".to_string())
+}
+
 /// Securely delete a file by overwriting with zeros, flushing to disk,
 /// then removing. This prevents recovery of secret data from deleted files.
 pub fn secure_delete(path: &std::path::Path) -> Result<(), String> {
@@ -125,21 +499,33 @@ mod tests {
     #[test]
     fn derive_key_deterministic() {
         let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
-        let key1 = derive_secrets_key(mnemonic).unwrap();
-        let key2 = derive_secrets_key(mnemonic).unwrap();
+        let key1 = derive_secrets_key(mnemonic).unwrap().into_key();
+        let key2 = derive_secrets_key(mnemonic).unwrap().into_key();
         assert_eq!(key1, key2);
     }
 
     #[test]
     fn different_mnemonics_different_keys() {
-        let key1 = derive_secrets_key("alpha bravo charlie delta echo foxtrot").unwrap();
-        let key2 = derive_secrets_key("golf hotel india juliet kilo lima").unwrap();
+        let key1 = derive_secrets_key("alpha bravo charlie delta echo foxtrot").unwrap().into_key();
+        let key2 = derive_secrets_key("golf hotel india juliet kilo lima").unwrap().into_key();
         assert_ne!(key1, key2);
     }
 
+    #[test]
+    fn ledger_derive_key_deterministic_and_domain_separated() {
+        let sig = b"fake device signature bytes";
+        let key1 = derive_secrets_key_from_signature(sig).unwrap().into_key();
+        let key2 = derive_secrets_key_from_signature(sig).unwrap().into_key();
+        assert_eq!(key1, key2);
+
+        // Same bytes through the mnemonic path must not collide.
+        let mnemonic_key = derive_secrets_key(std::str::from_utf8(sig).unwrap()).unwrap().into_key();
+        assert_ne!(key1, mnemonic_key);
+    }
+
     #[test]
     fn encrypt_decrypt_roundtrip() {
-        let key = derive_secrets_key("test mnemonic phrase for unit testing").unwrap();
+        let key = derive_secrets_key("test mnemonic phrase for unit testing").unwrap().into_key();
         let plaintext = b"secret scalar a=0xdeadbeef";
 
         let encrypted = encrypt_secret(&key, plaintext).unwrap();
@@ -149,10 +535,58 @@ mod tests {
         assert_eq!(decrypted, plaintext);
     }
 
+    #[test]
+    fn v2_encrypt_decrypt_roundtrip() {
+        let dir = std::env::temp_dir().join("peace_test_nonce_seq_roundtrip");
+        let _ = std::fs::remove_dir_all(&dir);
+        let nonce_seq = NonceSequence::new(dir.join("nonce_counter"));
+
+        let key = derive_secrets_key("test mnemonic phrase for unit testing").unwrap().into_key();
+        let plaintext = b"secret scalar a=0xdeadbeef";
+
+        let encrypted = encrypt_secret_v2(&key, plaintext, &nonce_seq).unwrap();
+        assert_eq!(encrypted.version, 2);
+
+        let decrypted = decrypt_secret(&key, &encrypted).unwrap();
+        assert_eq!(decrypted, plaintext);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn v2_nonces_never_repeat_across_calls() {
+        let dir = std::env::temp_dir().join("peace_test_nonce_seq_no_repeat");
+        let _ = std::fs::remove_dir_all(&dir);
+        let nonce_seq = NonceSequence::new(dir.join("nonce_counter"));
+        let key = [3u8; 32];
+
+        let first = encrypt_secret_v2(&key, b"one", &nonce_seq).unwrap();
+        let second = encrypt_secret_v2(&key, b"two", &nonce_seq).unwrap();
+        assert_ne!(first.nonce, second.nonce);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn v2_nonce_counter_survives_across_instances() {
+        let dir = std::env::temp_dir().join("peace_test_nonce_seq_persisted");
+        let _ = std::fs::remove_dir_all(&dir);
+        let counter_path = dir.join("nonce_counter");
+        let key = [9u8; 32];
+
+        let first = encrypt_secret_v2(&key, b"one", &NonceSequence::new(counter_path.clone())).unwrap();
+        // A fresh `NonceSequence` over the same path picks up where the
+        // last one left off instead of restarting from zero.
+        let second = encrypt_secret_v2(&key, b"two", &NonceSequence::new(counter_path.clone())).unwrap();
+        assert_ne!(first.nonce, second.nonce);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn wrong_key_fails() {
-        let key1 = derive_secrets_key("correct mnemonic").unwrap();
-        let key2 = derive_secrets_key("wrong mnemonic").unwrap();
+        let key1 = derive_secrets_key("correct mnemonic").unwrap().into_key();
+        let key2 = derive_secrets_key("wrong mnemonic").unwrap().into_key();
 
         let encrypted = encrypt_secret(&key1, b"secret data").unwrap();
         let result = decrypt_secret(&key2, &encrypted);
@@ -178,4 +612,67 @@ mod tests {
         let path = std::path::Path::new("/tmp/peace_test_nonexistent_secret.json");
         assert!(secure_delete(path).is_ok());
     }
+
+    #[test]
+    fn vault_encrypt_decrypt_roundtrip() {
+        let plaintext = b"{\"entries\":[]}";
+        let encrypted = encrypt_with_passphrase(plaintext, "export passphrase").unwrap();
+        let decrypted = decrypt_with_passphrase(&encrypted, "export passphrase").unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn vault_wrong_passphrase_fails() {
+        let encrypted = encrypt_with_passphrase(b"secret vault bytes", "correct").unwrap();
+        assert!(decrypt_with_passphrase(&encrypted, "wrong").is_err());
+    }
+
+    #[test]
+    fn secrets_key_locked_by_default() {
+        let key_state = SecretsKey::new();
+        assert!(key_state.get().is_err());
+    }
+
+    #[test]
+    fn secrets_key_set_then_get_roundtrip() {
+        let key_state = SecretsKey::new();
+        key_state.set([7u8; 32]).unwrap();
+        assert_eq!(key_state.get().unwrap(), [7u8; 32]);
+    }
+
+    #[test]
+    fn secrets_key_lock_now_zeroizes() {
+        let key_state = SecretsKey::new();
+        key_state.set([7u8; 32]).unwrap();
+        key_state.lock_now().unwrap();
+        assert!(key_state.get().is_err());
+    }
+
+    #[test]
+    fn secrets_key_expires_and_zeroizes_after_idle_timeout() {
+        let key_state = SecretsKey::new();
+        key_state.set([7u8; 32]).unwrap();
+        key_state
+            .set_idle_timeout(std::time::Duration::from_millis(10))
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(30));
+        assert!(key_state.get().is_err());
+        // Expiry should have wiped it — a second call still sees it locked.
+        assert!(key_state.get().is_err());
+    }
+
+    #[test]
+    fn secrets_key_get_slides_deadline_forward() {
+        let key_state = SecretsKey::new();
+        key_state.set([7u8; 32]).unwrap();
+        key_state
+            .set_idle_timeout(std::time::Duration::from_millis(50))
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(30));
+        // Still within the window — this access should succeed and reset it.
+        assert!(key_state.get().is_ok());
+        std::thread::sleep(std::time::Duration::from_millis(30));
+        // 30ms after the slide, well under the 50ms timeout from that access.
+        assert!(key_state.get().is_ok());
+    }
 }
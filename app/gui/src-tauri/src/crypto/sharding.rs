@@ -0,0 +1,311 @@
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A single Shamir share of a secret split by `split_secret`.
+///
+/// `data` holds one share byte per original secret byte, evaluated
+/// at `x` — the same layout `EncryptedSecret` uses for its own byte blobs,
+/// hex-encoded rather than raw so the struct round-trips through JSON
+/// cleanly next to a wallet's other exported files.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Share {
+    /// Number of shares required to reconstruct the secret. Carried on
+    /// every share so `combine_shares` can reject a mismatched set instead
+    /// of silently reconstructing garbage from shares of different splits.
+    pub threshold: u8,
+    /// This share's evaluation point (1..=n). Never 0 — `x = 0` is the
+    /// secret itself, never handed out as a share.
+    pub x: u8,
+    /// Per-byte share values, hex-encoded.
+    pub data: String,
+    /// SHA-256 of the original secret, hex-encoded, so `combine_shares` can
+    /// confirm a reconstruction actually recovered the right bytes rather
+    /// than silently returning whatever garbage comes out of combining
+    /// shares from two different splits.
+    pub checksum: String,
+}
+
+/// GF(256) multiplication under the AES reduction polynomial (x^8 + x^4 +
+/// x^3 + x + 1, i.e. 0x11b), via log/exp tables built once at first use.
+struct Gf256Tables {
+    exp: [u8; 512],
+    log: [u8; 256],
+}
+
+fn gf256_tables() -> &'static Gf256Tables {
+    use std::sync::OnceLock;
+    static TABLES: OnceLock<Gf256Tables> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut exp = [0u8; 512];
+        let mut log = [0u8; 256];
+        let mut x: u16 = 1;
+        for i in 0..255usize {
+            exp[i] = x as u8;
+            log[x as usize] = i as u8;
+            // Step by the generator 0x03 (x * 2 ^ x); 0x02 only has order
+            // 51 under 0x11b and would leave most of `log` unfilled.
+            x ^= x << 1;
+            if x & 0x100 != 0 {
+                x ^= 0x11b;
+            }
+        }
+        // Mirror the table past 255 so multiplication can index `exp[a + b]`
+        // without wrapping.
+        for i in 255..512 {
+            exp[i] = exp[i - 255];
+        }
+        Gf256Tables { exp, log }
+    })
+}
+
+fn gf256_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    let tables = gf256_tables();
+    let log_sum = tables.log[a as usize] as usize + tables.log[b as usize] as usize;
+    tables.exp[log_sum]
+}
+
+/// Evaluate the degree-`(k-1)` polynomial with the given coefficients
+/// (`coeffs[0]` is the constant term, i.e. the secret byte) at `x`, in
+/// GF(256), via Horner's method.
+fn gf256_eval(coeffs: &[u8], x: u8) -> u8 {
+    let mut result = 0u8;
+    for &coeff in coeffs.iter().rev() {
+        result = gf256_mul(result, x) ^ coeff;
+    }
+    result
+}
+
+/// Split `secret` into `n` shares, any `k` of which reconstruct it.
+///
+/// Classic Shamir secret sharing over GF(256): each byte of `secret` is the
+/// constant term of its own independent degree-`(k-1)` polynomial with
+/// random coefficients, evaluated at `x = 1..=n` to produce that share's
+/// byte. A user who loses their mnemonic but kept `k` of the `n` shares
+/// (e.g. handed to trusted contacts) can recover it without ever having
+/// `k-1` or fewer shares reveal anything about the secret.
+pub fn split_secret(secret: &[u8], k: u8, n: u8) -> Result<Vec<Share>, String> {
+    if k == 0 || n == 0 {
+        return Err("Threshold and share count must both be at least 1".to_string());
+    }
+    if k > n {
+        return Err("Threshold cannot exceed the number of shares".to_string());
+    }
+    if secret.is_empty() {
+        return Err("Cannot split an empty secret".to_string());
+    }
+
+    let checksum = to_hex(&Sha256::digest(secret));
+
+    // One random polynomial per secret byte, (k-1) random coefficients plus
+    // the byte itself as the constant term.
+    let mut rng = rand::rngs::OsRng;
+    let mut coeffs = vec![vec![0u8; k as usize]; secret.len()];
+    for (byte_idx, byte) in secret.iter().enumerate() {
+        coeffs[byte_idx][0] = *byte;
+        if k > 1 {
+            let mut random_tail = vec![0u8; (k - 1) as usize];
+            rng.fill_bytes(&mut random_tail);
+            coeffs[byte_idx][1..].copy_from_slice(&random_tail);
+        }
+    }
+
+    let mut shares = Vec::with_capacity(n as usize);
+    for x in 1..=n {
+        let data: Vec<u8> = coeffs.iter().map(|c| gf256_eval(c, x)).collect();
+        shares.push(Share {
+            threshold: k,
+            x,
+            data: to_hex(&data),
+            checksum: checksum.clone(),
+        });
+    }
+
+    Ok(shares)
+}
+
+/// Reconstruct the original secret from at least `threshold` shares.
+///
+/// Validates the shares agree on `threshold` and carry distinct `x` values
+/// before reconstructing each byte via Lagrange interpolation at `x = 0`,
+/// then confirms the result against the embedded checksum — catching
+/// shares drawn from two different splits, which would otherwise combine
+/// into silent garbage rather than a decode error.
+pub fn combine_shares(shares: &[Share]) -> Result<Vec<u8>, String> {
+    if shares.is_empty() {
+        return Err("No shares provided".to_string());
+    }
+
+    let threshold = shares[0].threshold;
+    if shares.iter().any(|s| s.threshold != threshold) {
+        return Err("Shares disagree on threshold — they're not from the same split".to_string());
+    }
+    if (shares.len() as u8) < threshold {
+        return Err(format!(
+            "Need at least {} shares to reconstruct, got {}",
+            threshold,
+            shares.len()
+        ));
+    }
+
+    let mut seen_x = std::collections::HashSet::new();
+    for s in shares {
+        if !seen_x.insert(s.x) {
+            return Err(format!("Duplicate share at x={}", s.x));
+        }
+    }
+
+    let checksum = shares[0].checksum.clone();
+    if shares.iter().any(|s| s.checksum != checksum) {
+        return Err("Shares disagree on checksum — they're not from the same split".to_string());
+    }
+
+    let decoded: Vec<Vec<u8>> = shares
+        .iter()
+        .map(|s| from_hex(&s.data))
+        .collect::<Result<_, _>>()?;
+
+    let secret_len = decoded[0].len();
+    if decoded.iter().any(|d| d.len() != secret_len) {
+        return Err("Shares disagree on secret length".to_string());
+    }
+
+    let xs: Vec<u8> = shares.iter().map(|s| s.x).collect();
+    let mut secret = Vec::with_capacity(secret_len);
+    for byte_idx in 0..secret_len {
+        let ys: Vec<u8> = decoded.iter().map(|d| d[byte_idx]).collect();
+        secret.push(lagrange_interpolate_at_zero(&xs, &ys));
+    }
+
+    let actual_checksum = to_hex(&Sha256::digest(&secret));
+    if actual_checksum != checksum {
+        return Err("Reconstructed secret failed checksum — wrong or insufficient shares".to_string());
+    }
+
+    Ok(secret)
+}
+
+/// Lagrange-interpolate the polynomial through `(xs[i], ys[i])` at `x = 0`,
+/// in GF(256). Division is multiplication by the GF(256) inverse, found via
+/// the log table (`exp[255 - log[a]]`, since every nonzero element has
+/// order dividing 255).
+fn lagrange_interpolate_at_zero(xs: &[u8], ys: &[u8]) -> u8 {
+    let tables = gf256_tables();
+    let mut result = 0u8;
+
+    for i in 0..xs.len() {
+        let mut numerator = 1u8;
+        let mut denominator = 1u8;
+        for j in 0..xs.len() {
+            if i == j {
+                continue;
+            }
+            // Term for x=0: (0 - xs[j]) / (xs[i] - xs[j]); subtraction is
+            // XOR in GF(256), so (0 - xs[j]) == xs[j].
+            numerator = gf256_mul(numerator, xs[j]);
+            denominator = gf256_mul(denominator, xs[i] ^ xs[j]);
+        }
+        let inv_denominator = tables.exp[255 - tables.log[denominator as usize] as usize];
+        let term = gf256_mul(gf256_mul(ys[i], numerator), inv_denominator);
+        result ^= term;
+    }
+
+    result
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+    if hex.len() % 2 != 0 {
+        return Err("Invalid hex: odd length".to_string());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| format!("Invalid hex: {e}")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_and_combine_exact_threshold_roundtrip() {
+        let secret = b"correct horse battery staple mnemonic entropy";
+        let shares = split_secret(secret, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        let subset = vec![shares[1].clone(), shares[3].clone(), shares[4].clone()];
+        let recovered = combine_shares(&subset).unwrap();
+        assert_eq!(recovered, secret);
+    }
+
+    #[test]
+    fn any_k_subset_reconstructs() {
+        let secret = b"some secret bytes to split and recover";
+        let shares = split_secret(secret, 2, 4).unwrap();
+
+        for i in 0..shares.len() {
+            for j in (i + 1)..shares.len() {
+                let subset = vec![shares[i].clone(), shares[j].clone()];
+                assert_eq!(combine_shares(&subset).unwrap(), secret);
+            }
+        }
+    }
+
+    #[test]
+    fn fewer_than_threshold_fails() {
+        let secret = b"needs three shares";
+        let shares = split_secret(secret, 3, 5).unwrap();
+        let subset = vec![shares[0].clone(), shares[1].clone()];
+        assert!(combine_shares(&subset).is_err());
+    }
+
+    #[test]
+    fn duplicate_x_rejected() {
+        let secret = b"no duplicates allowed";
+        let shares = split_secret(secret, 2, 3).unwrap();
+        let subset = vec![shares[0].clone(), shares[0].clone()];
+        assert!(combine_shares(&subset).is_err());
+    }
+
+    #[test]
+    fn mismatched_threshold_rejected() {
+        let secret_a = b"first split secret......";
+        let secret_b = b"second split secret.....";
+        let shares_a = split_secret(secret_a, 2, 3).unwrap();
+        let shares_b = split_secret(secret_b, 3, 3).unwrap();
+
+        let mixed = vec![shares_a[0].clone(), shares_b[0].clone(), shares_b[1].clone()];
+        assert!(combine_shares(&mixed).is_err());
+    }
+
+    #[test]
+    fn mismatched_split_fails_checksum() {
+        let shares_a = split_secret(b"alpha secret value......", 2, 3).unwrap();
+        let shares_b = split_secret(b"bravo secret value......", 2, 3).unwrap();
+
+        // Same threshold, different splits — checksum catches what the
+        // threshold check alone wouldn't.
+        let mixed = vec![
+            Share {
+                threshold: shares_b[1].threshold,
+                x: shares_b[1].x,
+                data: shares_b[1].data.clone(),
+                checksum: shares_a[0].checksum.clone(),
+            },
+            shares_a[0].clone(),
+        ];
+        assert!(combine_shares(&mixed).is_err());
+    }
+
+    #[test]
+    fn rejects_threshold_above_share_count() {
+        assert!(split_secret(b"secret", 4, 3).is_err());
+    }
+}
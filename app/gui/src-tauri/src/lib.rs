@@ -1,21 +1,104 @@
-mod commands;
-mod config;
-mod crypto;
-mod process;
+pub mod commands;
+pub mod config;
+pub mod crypto;
+pub mod process;
 
+use commands::backup::BackupHttp;
+use commands::iagon::{IagonApiVersion, IagonHttp};
 use commands::media::MediaDir;
 use commands::secrets::SecretsDir;
 use commands::wallet::WalletState;
 use config::AppConfig;
+use crypto::kv_store::EncryptedStore;
 use crypto::secrets::SecretsKey;
+use process::instance_lock::InstanceLock;
 use process::manager::NodeManager;
+use process::ogmios_client::OgmiosClient;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use tauri::Manager;
+use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
 
 /// Global flag to prevent duplicate shutdown attempts.
 static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
 
+/// Build and `.manage()` every piece of application state the command layer
+/// depends on: wallet path, loaded `AppConfig`, the persistent `OgmiosClient`,
+/// `NodeManager`, the on-disk secrets directory, `SecretsKey`, and the cached-
+/// media directories. Shared between `run()` (the GUI, after the window is
+/// set up) and `bin/peace-cli.rs` (the headless CLI, which never creates a
+/// window) so both drive the exact same `#[tauri::command]` functions against
+/// identically-constructed state rather than two parallel initialization
+/// paths drifting apart.
+pub fn managed_state(app_handle: &tauri::AppHandle, app_data_dir: &Path) -> Result<(), String> {
+    // Wallet state (Phase 1)
+    let wallet_path = app_data_dir.join("wallet.json");
+    app_handle.manage(WalletState {
+        wallet_path,
+        mnemonic: Mutex::new(None),
+    });
+
+    // App config — reads from bundled resources/config.json
+    let resource_dir = app_handle
+        .path()
+        .resource_dir()
+        .unwrap_or_else(|_| app_data_dir.to_path_buf());
+    let app_config = AppConfig::load(&resource_dir)?;
+
+    // Persistent Ogmios connection. Connects (and reconnects with backoff)
+    // in the background, so this is safe to create before Ogmios has even
+    // been started.
+    app_handle.manage(OgmiosClient::connect(app_config.ogmios_port));
+
+    app_handle.manage(app_config);
+
+    // Node manager (Phase 2)
+    let node_manager = NodeManager::new(app_handle.clone());
+    app_handle.manage(node_manager);
+
+    // Secret storage directory (filesystem-backed, survives WebView resets)
+    let secrets_dir = app_data_dir.join("secrets");
+    std::fs::create_dir_all(&secrets_dir)
+        .map_err(|e| format!("Failed to create secrets directory: {e}"))?;
+    app_handle.manage(SecretsDir(secrets_dir.clone()));
+
+    // Secrets encryption key (derived from mnemonic on wallet unlock)
+    app_handle.manage(SecretsKey::new());
+
+    // General-purpose encrypted key/value vault the wallet can stash
+    // arbitrary secrets in — a subdirectory of the same secrets root, with
+    // its own nonce counter and index so it doesn't collide with the
+    // scalar secret files `commands::secrets` manages directly.
+    app_handle.manage(EncryptedStore::new(secrets_dir.join("vault")));
+
+    // Shared pooled HTTP client for the Iagon storage proxy commands — built
+    // once here rather than per-call, so keep-alive connections and the TLS
+    // session cache survive across requests. Reads any saved proxy/resolver
+    // settings from the secrets directory so they apply from the first
+    // request onward.
+    app_handle.manage(IagonHttp::new(&secrets_dir));
+
+    // Negotiated Iagon API version — unset until `iagon_check_api_version`
+    // runs, at which point every Iagon command starts building requests
+    // against whatever version it found instead of the build's default.
+    app_handle.manage(IagonApiVersion::new());
+
+    // Shared HTTP client for the optional encrypted backup sync commands —
+    // same "build once, reuse the connection pool" rationale as `IagonHttp`.
+    app_handle.manage(BackupHttp::new());
+
+    // Media directory (for cached images, future video/docs)
+    let media_images_dir = app_data_dir.join("media").join("images");
+    std::fs::create_dir_all(&media_images_dir)
+        .map_err(|e| format!("Failed to create media/images directory: {e}"))?;
+    let _ = std::fs::create_dir_all(app_data_dir.join("media").join("video"));
+    let _ = std::fs::create_dir_all(app_data_dir.join("media").join("docs"));
+    app_handle.manage(MediaDir(media_images_dir));
+
+    Ok(())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Workaround for WebKitGTK crashes on newer kernels (6.17+) and older GPUs
@@ -28,46 +111,57 @@ pub fn run() {
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_dialog::init())
         .setup(|app| {
             let app_data_dir = app
                 .path()
                 .app_data_dir()
                 .expect("Failed to resolve app data directory");
 
-            // Wallet state (Phase 1)
-            let wallet_path = app_data_dir.join("wallet.json");
-            app.manage(WalletState {
-                wallet_path,
-                mnemonic: Mutex::new(None),
-            });
+            // Single-instance advisory lock — must be acquired before
+            // anything below touches wallet.json, the secrets directory, or
+            // the node db, since a second instance racing this one is
+            // exactly the window this closes. A pre-existing lock means
+            // another instance is running: tell the user and exit instead
+            // of starting the NodeManager.
+            match InstanceLock::try_acquire(&app_data_dir) {
+                Ok(Some(lock)) => {
+                    app.manage(lock);
+                }
+                Ok(None) => {
+                    app.handle()
+                        .dialog()
+                        .message(
+                            "Peace Protocol is already running. Only one instance can run at a time.",
+                        )
+                        .title("Already Running")
+                        .kind(MessageDialogKind::Error)
+                        .blocking_show();
+                    app.handle().exit(1);
+                    return Ok(());
+                }
+                Err(e) => {
+                    // A missing/unwritable lock file shouldn't block startup
+                    // on its own — log it and proceed unprotected.
+                    eprintln!("[instance-lock] {e}");
+                }
+            }
 
-            // App config — reads from bundled resources/config.json
-            let resource_dir = app
-                .path()
-                .resource_dir()
-                .unwrap_or_else(|_| app_data_dir.clone());
-            let app_config = AppConfig::load(&resource_dir);
-            app.manage(app_config);
-
-            // Node manager (Phase 2)
-            let node_manager = NodeManager::new(app.handle().clone());
-            app.manage(node_manager);
-
-            // Secret storage directory (filesystem-backed, survives WebView resets)
-            let secrets_dir = app_data_dir.join("secrets");
-            std::fs::create_dir_all(&secrets_dir).expect("Failed to create secrets directory");
-            app.manage(SecretsDir(secrets_dir));
-
-            // Secrets encryption key (derived from mnemonic on wallet unlock)
-            app.manage(SecretsKey(Mutex::new(None)));
-
-            // Media directory (for cached images, future video/docs)
-            let media_images_dir = app_data_dir.join("media").join("images");
-            std::fs::create_dir_all(&media_images_dir)
-                .expect("Failed to create media/images directory");
-            let _ = std::fs::create_dir_all(app_data_dir.join("media").join("video"));
-            let _ = std::fs::create_dir_all(app_data_dir.join("media").join("docs"));
-            app.manage(MediaDir(media_images_dir));
+            // Surfaced the same way as the duplicate-instance case above
+            // rather than `.expect()`-panicking: a corrupt or incompatible
+            // config file (see `AppConfig::load`) is a user-actionable
+            // problem, not a crash a GUI user would ever see the message
+            // for otherwise.
+            if let Err(e) = managed_state(&app.handle().clone(), &app_data_dir) {
+                app.handle()
+                    .dialog()
+                    .message(format!("Failed to initialize application state: {e}"))
+                    .title("Startup Failed")
+                    .kind(MessageDialogKind::Error)
+                    .blocking_show();
+                app.handle().exit(1);
+                return Ok(());
+            }
 
             Ok(())
         })
@@ -101,10 +195,19 @@ pub fn run() {
             // Wallet commands (Phase 1)
             commands::wallet::wallet_exists,
             commands::wallet::create_wallet,
+            commands::wallet::create_wallet_ledger,
+            commands::wallet::wallet_is_hardware,
+            commands::wallet::ledger_get_address,
+            commands::wallet::ledger_sign_tx,
             commands::wallet::unlock_wallet,
             commands::wallet::lock_wallet,
+            commands::wallet::lock_now,
+            commands::wallet::set_auto_lock_timeout,
+            commands::wallet::change_wallet_password,
             commands::wallet::delete_wallet,
             commands::wallet::reveal_mnemonic,
+            commands::wallet::split_mnemonic_recovery_shares,
+            commands::wallet::combine_mnemonic_recovery_shares,
             // Node commands (Phase 2)
             commands::node::get_node_status,
             commands::node::get_process_status,
@@ -115,9 +218,12 @@ pub fn run() {
             // Config commands (Phase 2)
             commands::config::get_network,
             commands::config::set_network,
+            commands::config::set_custom_network,
             commands::config::get_data_dir,
             commands::config::get_app_config,
             commands::config::get_disk_usage,
+            commands::wizard::wizard_defaults,
+            commands::wizard::run_config_wizard,
             // SNARK commands (Phase 4)
             commands::snark::snark_check_setup,
             commands::snark::snark_decompress_setup,
@@ -126,10 +232,12 @@ pub fn run() {
             commands::snark::snark_prove,
             // Secret storage commands
             commands::secrets::store_seller_secrets,
+            commands::secrets::derive_seller_secrets,
             commands::secrets::get_seller_secrets,
             commands::secrets::remove_seller_secrets,
             commands::secrets::list_seller_secrets,
             commands::secrets::store_bid_secrets,
+            commands::secrets::derive_bid_secrets,
             commands::secrets::get_bid_secrets,
             commands::secrets::get_bid_secrets_for_encryption,
             commands::secrets::remove_bid_secrets,
@@ -137,12 +245,43 @@ pub fn run() {
             commands::secrets::get_accept_bid_secrets,
             commands::secrets::remove_accept_bid_secrets,
             commands::secrets::has_accept_bid_secrets,
+            commands::secrets::prune_expired_accept_bid_secrets,
+            commands::secrets::export_vault,
+            commands::secrets::import_vault,
             // Media commands (image caching)
             commands::media::download_image,
             commands::media::get_cached_image,
             commands::media::list_cached_images,
             commands::media::ban_image,
             commands::media::unban_image,
+            // Iagon storage proxy commands
+            commands::iagon::iagon_get_nonce,
+            commands::iagon::iagon_verify,
+            commands::iagon::iagon_generate_api_key,
+            commands::iagon::iagon_verify_api_key,
+            commands::iagon::iagon_upload,
+            commands::iagon::iagon_upload_encrypted,
+            commands::iagon::iagon_upload_path,
+            commands::iagon::iagon_download,
+            commands::iagon::iagon_download_encrypted,
+            commands::iagon::iagon_download_to_path,
+            commands::iagon::iagon_delete_file,
+            commands::iagon::get_iagon_transport_config,
+            commands::iagon::set_iagon_transport_config,
+            commands::iagon::test_iagon_connection,
+            commands::iagon::iagon_check_api_version,
+            commands::iagon::get_iagon_audit_log,
+            commands::iagon::clear_iagon_audit_log,
+            // Encrypted cloud backup sync commands
+            commands::backup::get_backup_server_config,
+            commands::backup::set_backup_server_config,
+            commands::backup::backup_push_secret,
+            commands::backup::backup_pull_secret,
+            // General-purpose encrypted vault commands
+            commands::vault::vault_put,
+            commands::vault::vault_get,
+            commands::vault::vault_remove,
+            commands::vault::vault_list_keys,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
@@ -1,5 +1,5 @@
 use crate::config::AppConfig;
-use crate::process::manager::NodeManager;
+use crate::process::manager::{NodeManager, RestartPolicy};
 use std::path::PathBuf;
 
 /// Paths to all config files needed by cardano-node
@@ -130,6 +130,17 @@ pub async fn start_cardano_node(
         let _ = std::fs::remove_file(&lock_file);
     }
 
+    manager
+        .set_restart_policy(
+            "cardano-node",
+            RestartPolicy {
+                max_retries: app_config.max_restarts,
+                initial_delay_ms: app_config.restart_backoff_ms,
+                ..RestartPolicy::default()
+            },
+        )
+        .await;
+
     let args = config.build_args();
     manager
         .start("cardano-node", "cardano-node", args)
@@ -138,8 +149,15 @@ pub async fn start_cardano_node(
 
 /// Check if cardano-node has a database (i.e., has been bootstrapped).
 /// Mithril v1 extracts to `node-db/db/`, so we check for markers there.
+///
+/// Also requires the verified-digest marker written by
+/// `mithril::bootstrap_and_verify` on a successful bootstrap, so a
+/// half-extracted or tampered `db/` left behind by a crashed or
+/// interrupted bootstrap is detected and re-bootstrapped rather than
+/// trusted just because the directory happens to exist.
 pub fn has_chain_data(app_config: &AppConfig, app_data_dir: &PathBuf) -> bool {
-    let db_dir = app_config.node_db_dir(app_data_dir).join("db");
-    db_dir.join("protocolMagicId").exists()
-        || db_dir.join("immutable").exists()
+    let node_db_dir = app_config.node_db_dir(app_data_dir);
+    let db_dir = node_db_dir.join("db");
+    let has_markers = db_dir.join("protocolMagicId").exists() || db_dir.join("immutable").exists();
+    has_markers && super::mithril::recorded_digest(&node_db_dir).is_some()
 }
@@ -1,6 +1,31 @@
 use crate::config::AppConfig;
-use crate::process::manager::NodeManager;
+use crate::process::manager::{NodeManager, ProbeResult, RestartPolicy};
 use std::path::PathBuf;
+use tauri::Manager;
+
+/// Resolve the directory containing the built Express backend (`dist/index.js`).
+/// Mirrors `CardanoNodeConfig::ensure_config_files`'s resource resolution: in
+/// production it's bundled under the resource dir, in dev it lives in the
+/// source tree instead since `resource_dir` points at `target/debug/`.
+pub fn resolve_be_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let resource_dir = app_handle
+        .path()
+        .resource_dir()
+        .map_err(|e| format!("Failed to resolve resource dir: {e}"))?;
+
+    let prod_path = resource_dir.join("resources").join("express");
+    let dev_path = resource_dir
+        .parent() // target/
+        .and_then(|p| p.parent()) // src-tauri/
+        .map(|p| p.join("resources").join("express"));
+
+    [dev_path.as_deref(), Some(prod_path.as_path())]
+        .into_iter()
+        .flatten()
+        .find(|p| p.exists())
+        .map(|p| p.to_path_buf())
+        .ok_or_else(|| "Express backend directory not found in resources".to_string())
+}
 
 /// Start the Express backend as a child process.
 /// Unlike the sidecar-based processes, Express is spawned via tokio::process::Command
@@ -13,7 +38,44 @@ pub async fn start_express(
     app_config: &AppConfig,
     be_dir: &PathBuf,
 ) -> Result<(), String> {
-    let env_vars = app_config.express_env_vars();
+    manager
+        .set_restart_policy(
+            "express",
+            RestartPolicy {
+                max_retries: app_config.max_restarts,
+                initial_delay_ms: app_config.restart_backoff_ms,
+                ..RestartPolicy::default()
+            },
+        )
+        .await;
+
+    manager
+        .set_probe("express", || async {
+            if health_check().await {
+                ProbeResult::Ready
+            } else {
+                ProbeResult::Unhealthy("Express health check failed".to_string())
+            }
+        })
+        .await;
+
+    // Keep port 3001 bound across crash-restarts: the bundled backend reads
+    // LISTEN_FDS/LISTEN_FD (set by `start_command` below) and, when present,
+    // calls `server.listen({fd})` on the inherited socket instead of binding
+    // its own — so a restart never has a window where the port is closed.
+    // Best-effort: if the port's already taken (e.g. a previous instance
+    // didn't exit fully), fall back to the existing rebind-on-restart path.
+    if let Err(e) = manager
+        .enable_socket_preserving_restart(
+            "express",
+            std::net::SocketAddr::from(([127, 0, 0, 1], 3001)),
+        )
+        .await
+    {
+        eprintln!("[express] Socket-preserving restart unavailable, falling back to rebind: {e}");
+    }
+
+    let env_vars = app_config.express_env_vars()?;
     manager.start_command("express", "node", vec!["dist/index.js".to_string()], Some(be_dir), env_vars).await
 }
 
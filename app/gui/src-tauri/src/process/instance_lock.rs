@@ -0,0 +1,43 @@
+use fd_lock::{RwLock, RwLockWriteGuard};
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+
+/// Advisory OS file lock on `<app_data_dir>/.instance.lock`, held for the
+/// process's whole lifetime so a second copy of the app can't race this one
+/// on `wallet.json`, the secrets directory, or the node db mid-Mithril-
+/// bootstrap. Acquired once in `run()`'s setup and stored in managed state;
+/// the OS releases it automatically on process exit (`flock` on Unix,
+/// `LockFileEx` on Windows), so there's no explicit release path to wire
+/// into the shutdown sequence.
+pub struct InstanceLock {
+    _guard: RwLockWriteGuard<'static, File>,
+}
+
+impl InstanceLock {
+    /// Try to acquire the lock. `Ok(None)` means another instance already
+    /// holds it — an expected, non-error outcome the caller should handle
+    /// by telling the user and exiting, not by treating it as I/O failure.
+    pub fn try_acquire(app_data_dir: &Path) -> Result<Option<Self>, String> {
+        std::fs::create_dir_all(app_data_dir)
+            .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+
+        let path = app_data_dir.join(".instance.lock");
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to open instance lock file: {e}"))?;
+
+        // Leaked deliberately: the lock lives for the process's entire
+        // lifetime, so there's no moment to drop it, and leaking sidesteps
+        // building a self-referential struct just to own both the
+        // `RwLock<File>` and a guard borrowed from it.
+        let lock: &'static mut RwLock<File> = Box::leak(Box::new(RwLock::new(file)));
+
+        match lock.try_write() {
+            Ok(guard) => Ok(Some(Self { _guard: guard })),
+            Err(_) => Ok(None),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::config::AppConfig;
-use crate::process::manager::NodeManager;
+use crate::process::manager::{NodeManager, ProbeResult, RestartPolicy};
 use std::path::Path;
 
 /// Build Kupo command-line arguments
@@ -108,6 +108,30 @@ pub async fn start_kupo(
     let patterns_json = serde_json::to_string(&patterns).unwrap_or_default();
     let _ = std::fs::write(&patterns_file, patterns_json);
 
+    manager
+        .set_restart_policy(
+            "kupo",
+            RestartPolicy {
+                max_retries: app_config.max_restarts,
+                initial_delay_ms: app_config.restart_backoff_ms,
+                ..RestartPolicy::default()
+            },
+        )
+        .await;
+
+    manager
+        .set_probe("kupo", {
+            let port = app_config.kupo_port;
+            move || async move {
+                match get_sync_progress(port).await {
+                    Ok(progress) if progress >= 0.999 => ProbeResult::Ready,
+                    Ok(progress) => ProbeResult::Syncing(progress),
+                    Err(e) => ProbeResult::Unhealthy(e),
+                }
+            }
+        })
+        .await;
+
     let args = build_kupo_args(app_config, app_data_dir, &patterns);
     manager.start("kupo", "kupo", args).await
 }
@@ -1,10 +1,120 @@
-use crate::config::AppConfig;
-use crate::process::manager::NodeManager;
-use serde::Serialize;
-use std::path::PathBuf;
+use crate::config::{AppConfig, NetworkCheckpoints};
+use crate::process::manager::{NodeManager, ProcessStatus};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Marker file written into `node-db/` after a bootstrap's snapshot has
+/// been downloaded, certificate-chain-verified, and extracted by
+/// mithril-client. Its content is the verified digest; its mere presence
+/// is what lets `has_chain_data` tell a complete bootstrap apart from a
+/// half-extracted or tampered `db/` left behind by a crash.
+const DIGEST_MARKER_FILENAME: &str = ".mithril-digest";
+
+/// Path to the verified-digest marker for a given node-db directory.
+pub fn digest_marker_path(node_db_dir: &Path) -> PathBuf {
+    node_db_dir.join(DIGEST_MARKER_FILENAME)
+}
+
+/// Read back the digest recorded by a prior successful bootstrap, if any.
+pub fn recorded_digest(node_db_dir: &Path) -> Option<String> {
+    std::fs::read_to_string(digest_marker_path(node_db_dir)).ok()
+}
+
+/// Marker file recording a streaming content digest over the extracted
+/// `db/immutable/` files at the moment a bootstrap finished. Distinct from
+/// `DIGEST_MARKER_FILENAME`: that one records mithril-client's own
+/// certificate-chain digest (an attestation the *download* wasn't
+/// tampered with); this one lets us detect whether the *extracted files
+/// on disk* have changed or been truncated since — mithril-client's HTTP
+/// stream isn't something this process can see bytes of directly (the
+/// download happens inside the `mithril-client` sidecar), so integrity is
+/// checked by re-hashing what's actually on disk instead of intercepting
+/// the download.
+const CONTENT_DIGEST_MARKER_FILENAME: &str = ".mithril-content-digest";
+
+fn content_digest_marker_path(node_db_dir: &Path) -> PathBuf {
+    node_db_dir.join(CONTENT_DIGEST_MARKER_FILENAME)
+}
+
+/// Adapts a `Sha256` hasher so `std::io::copy` can stream bytes straight
+/// into it one chunk at a time, without ever holding a whole file in memory.
+struct HashingWriter<'a>(&'a mut Sha256);
+
+impl std::io::Write for HashingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Stream-hash every file under `db/immutable/` in filename order,
+/// producing a single digest over their combined bytes. Each file is
+/// drained through `io::copy` in fixed-size chunks, so this runs in
+/// constant memory regardless of snapshot size.
+fn hash_immutable_files_streaming(db_dir: &Path) -> Result<String, String> {
+    let immutable_dir = db_dir.join("immutable");
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(&immutable_dir)
+        .map_err(|e| format!("Failed to read {}: {e}", immutable_dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .collect();
+    paths.sort();
+
+    if paths.is_empty() {
+        return Err(format!("No immutable files found under {}", immutable_dir.display()));
+    }
+
+    let mut hasher = Sha256::new();
+    for path in &paths {
+        let mut file =
+            std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+        std::io::copy(&mut file, &mut HashingWriter(&mut hasher))
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Re-hash the extracted snapshot and confirm it still matches the content
+/// digest recorded when the bootstrap finished. Called before starting
+/// cardano-node so a truncated or tampered `db/` is caught before the node
+/// tries to replay it, rather than failing (or worse, silently accepting
+/// bad data) deep inside cardano-node's own startup.
+///
+/// If no content-digest marker exists (e.g. chain data from before this
+/// check existed), verification is skipped rather than treated as failure.
+pub fn verify_snapshot_integrity(app_config: &AppConfig, app_data_dir: &PathBuf) -> Result<(), String> {
+    let node_db_dir = app_config.node_db_dir(app_data_dir);
+    let marker_path = content_digest_marker_path(&node_db_dir);
+    let Ok(expected) = std::fs::read_to_string(&marker_path) else {
+        return Ok(());
+    };
+
+    let db_dir = node_db_dir.join("db");
+    let actual = hash_immutable_files_streaming(&db_dir).map_err(|e| {
+        let _ = std::fs::remove_dir_all(&node_db_dir);
+        format!("Chain data is missing or unreadable, bootstrap will be required again: {e}")
+    })?;
+
+    if actual != expected.trim() {
+        let _ = std::fs::remove_dir_all(&node_db_dir);
+        return Err(
+            "Chain data failed integrity verification (truncated or tampered snapshot); \
+             deleted and a fresh Mithril bootstrap will be required."
+                .to_string(),
+        );
+    }
+
+    Ok(())
+}
 
 /// Progress of a Mithril bootstrap operation
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct MithrilProgress {
     pub stage: MithrilStage,
     pub progress_percent: f64,
@@ -14,20 +124,209 @@ pub struct MithrilProgress {
 }
 
 /// Stages of the Mithril bootstrap process
-#[derive(Clone, Serialize, PartialEq)]
+#[derive(Clone, Serialize, Deserialize, PartialEq)]
 pub enum MithrilStage {
     FetchingSnapshot,
     Downloading,
     Verifying,
     Extracting,
+    /// Between attempts of the resumable download supervisor in
+    /// `wait_and_finalize_bootstrap`, after a non-zero exit before
+    /// `Complete` and before the next retry is spawned.
+    Retrying,
     Complete,
     Error,
 }
 
-/// Fetch the latest snapshot digest from the Mithril aggregator API.
-/// The /artifact/snapshots endpoint returns an array with a "digest" field per entry.
-async fn fetch_latest_digest(aggregator_url: &str) -> Result<String, String> {
-    let url = format!("{}/artifact/snapshots", aggregator_url);
+/// Sidecar file recording the last observed `MithrilProgress`, so a bootstrap
+/// interrupted by a network drop or app restart can be resumed (and the UI
+/// can show where it left off) instead of silently looking like an empty
+/// chain data directory.
+const PROGRESS_MARKER_FILENAME: &str = "mithril-progress.json";
+
+fn progress_marker_path(node_db_dir: &Path) -> PathBuf {
+    node_db_dir.join(PROGRESS_MARKER_FILENAME)
+}
+
+/// Read back the last persisted bootstrap progress, if any.
+pub fn read_progress(node_db_dir: &Path) -> Option<MithrilProgress> {
+    let json = std::fs::read_to_string(progress_marker_path(node_db_dir)).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Persist the latest observed progress. Best-effort: a failure to write the
+/// sidecar shouldn't abort an otherwise-succeeding bootstrap.
+fn persist_progress(node_db_dir: &Path, progress: &MithrilProgress) {
+    if let Ok(json) = serde_json::to_string(progress) {
+        let _ = std::fs::write(progress_marker_path(node_db_dir), json);
+    }
+}
+
+/// If chain data isn't already present, and a prior bootstrap attempt left
+/// behind a progress sidecar that hadn't reached `Complete`, return that
+/// progress so the frontend can offer to resume rather than re-download
+/// from scratch. `None` means either chain data is already there, or there's
+/// nothing to resume (fresh start).
+pub fn bootstrap_resume_state(app_config: &AppConfig, app_data_dir: &PathBuf) -> Option<MithrilProgress> {
+    if super::cardano::has_chain_data(app_config, app_data_dir) {
+        return None;
+    }
+    let node_db_dir = app_config.node_db_dir(app_data_dir);
+    let progress = read_progress(&node_db_dir)?;
+    (progress.stage != MithrilStage::Complete).then_some(progress)
+}
+
+/// A candidate snapshot parsed from one entry of the aggregator's
+/// `/artifact/snapshots` list, with just enough fields to pin it against
+/// `NetworkCheckpoints`.
+#[derive(Debug, Clone)]
+struct SnapshotCandidate {
+    digest: String,
+    /// The aggregator's published schema has no single canonical "block
+    /// height" field, so this falls back to `immutable_file_number` (which
+    /// is monotonic per network) when a snapshot doesn't carry one
+    /// explicitly — that's what bundled checkpoints are pinned against in
+    /// practice.
+    block_height: u64,
+    immutable_file_number: u64,
+}
+
+fn parse_snapshot_candidate(snapshot: &serde_json::Value) -> Option<SnapshotCandidate> {
+    let digest = snapshot.get("digest")?.as_str()?.to_string();
+    let beacon = snapshot.get("beacon");
+    let immutable_file_number = beacon
+        .and_then(|b| b.get("immutable_file_number"))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    let block_height = snapshot
+        .get("block_height")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(immutable_file_number);
+
+    Some(SnapshotCandidate {
+        digest,
+        block_height,
+        immutable_file_number,
+    })
+}
+
+/// Why a candidate snapshot was rejected against the bundled
+/// `NetworkCheckpoints` — kept as a typed enum (rather than flattened to a
+/// `String` immediately) so a caller closer to the UI can tell these cases
+/// apart instead of pattern-matching an error message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckpointError {
+    /// The candidate's height is below the newest bundled checkpoint —
+    /// the aggregator is offering a stale (or rolled-back) snapshot.
+    HeightRegressed { candidate_height: u64, checkpoint_height: u64 },
+    /// The candidate's height matches a bundled checkpoint, but its digest
+    /// doesn't — the aggregator is lying about what that snapshot contains.
+    DigestMismatch { height: u64, expected: String, actual: String },
+    /// The candidate's immutable file number is below the network's
+    /// configured minimum.
+    BelowMinimumImmutableFileNumber { candidate: u64, minimum: u64 },
+}
+
+impl std::fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckpointError::HeightRegressed { candidate_height, checkpoint_height } => write!(
+                f,
+                "aggregator returned a snapshot at height {candidate_height}, older than the last trusted checkpoint at height {checkpoint_height}"
+            ),
+            CheckpointError::DigestMismatch { height, expected, actual } => write!(
+                f,
+                "snapshot at height {height} has digest {actual} but the bundled checkpoint expects {expected}"
+            ),
+            CheckpointError::BelowMinimumImmutableFileNumber { candidate, minimum } => write!(
+                f,
+                "snapshot's immutable file number {candidate} is below the minimum accepted ({minimum}) for this network"
+            ),
+        }
+    }
+}
+
+/// Check a candidate snapshot against the network's bundled checkpoints:
+/// reject anything below the configured minimum immutable file number or
+/// older than the newest checkpoint, and if the candidate's height matches
+/// a bundled checkpoint exactly, require its digest to match too.
+fn verify_against_checkpoint(
+    candidate: &SnapshotCandidate,
+    checkpoints: &NetworkCheckpoints,
+) -> Result<(), CheckpointError> {
+    if candidate.immutable_file_number < checkpoints.min_immutable_file_number {
+        return Err(CheckpointError::BelowMinimumImmutableFileNumber {
+            candidate: candidate.immutable_file_number,
+            minimum: checkpoints.min_immutable_file_number,
+        });
+    }
+
+    if let Some(newest) = checkpoints.checkpoints.iter().max_by_key(|c| c.block_height) {
+        if candidate.block_height < newest.block_height {
+            return Err(CheckpointError::HeightRegressed {
+                candidate_height: candidate.block_height,
+                checkpoint_height: newest.block_height,
+            });
+        }
+    }
+
+    if let Some(checkpoint) = checkpoints
+        .checkpoints
+        .iter()
+        .find(|c| c.block_height == candidate.block_height)
+    {
+        if checkpoint.digest != candidate.digest {
+            return Err(CheckpointError::DigestMismatch {
+                height: candidate.block_height,
+                expected: checkpoint.digest.clone(),
+                actual: candidate.digest.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetch the latest trustworthy snapshot digest from the Mithril aggregator
+/// API. Unlike blindly taking the first array entry, each candidate is
+/// checked with `verify_against_checkpoint` against this network's bundled
+/// checkpoints before being accepted — a compromised or spoofed aggregator
+/// can't steer a bootstrap onto a snapshot older than (or different from)
+/// one already known good.
+///
+/// A network can configure more than one aggregator URL; they're tried in a
+/// randomly shuffled order (`mithril_aggregator_urls_shuffled`) so a single
+/// down or overloaded aggregator doesn't block every bootstrap, and repeated
+/// bootstraps don't all hammer the same one. The URL that actually served a
+/// verifiable snapshot is returned alongside the digest so
+/// `start_mithril_bootstrap` can point mithril-client's `--aggregator-endpoint`
+/// at the same one rather than re-resolving (and re-shuffling) independently.
+async fn fetch_latest_digest(app_config: &AppConfig) -> Result<(String, String), String> {
+    let checkpoints = app_config.mithril_checkpoints_for_network();
+    let urls = app_config.mithril_aggregator_urls_shuffled();
+    if urls.is_empty() {
+        return Err("No Mithril aggregator URL configured for this network".to_string());
+    }
+
+    let mut last_error: Option<String> = None;
+
+    for aggregator_url in urls {
+        match fetch_latest_digest_from(&aggregator_url, &checkpoints).await {
+            Ok(digest) => return Ok((digest, aggregator_url)),
+            Err(e) => last_error = Some(format!("{aggregator_url}: {e}")),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| "No Mithril aggregator URL configured for this network".to_string()))
+}
+
+/// Query a single aggregator's `/artifact/snapshots` endpoint and return the
+/// first candidate that passes `verify_against_checkpoint`.
+async fn fetch_latest_digest_from(
+    aggregator_url: &str,
+    checkpoints: &NetworkCheckpoints,
+) -> Result<String, String> {
+    let url = format!("{aggregator_url}/artifact/snapshots");
     let resp = reqwest::get(&url)
         .await
         .map_err(|e| format!("Failed to query Mithril aggregator: {e}"))?;
@@ -35,28 +334,203 @@ async fn fetch_latest_digest(aggregator_url: &str) -> Result<String, String> {
         .json()
         .await
         .map_err(|e| format!("Failed to parse Mithril snapshot list: {e}"))?;
-    let digest = json
+    let snapshots = json
         .as_array()
-        .and_then(|arr| arr.first())
-        .and_then(|snap| snap.get("digest"))
-        .and_then(|d| d.as_str())
         .ok_or_else(|| "No snapshots available from Mithril aggregator".to_string())?;
-    Ok(digest.to_string())
+
+    let mut last_rejection: Option<CheckpointError> = None;
+
+    for snapshot in snapshots {
+        let Some(candidate) = parse_snapshot_candidate(snapshot) else {
+            continue;
+        };
+        match verify_against_checkpoint(&candidate, checkpoints) {
+            Ok(()) => return Ok(candidate.digest),
+            Err(e) => last_rejection = Some(e),
+        }
+    }
+
+    Err(match last_rejection {
+        Some(e) => e.to_string(),
+        None => "No snapshots available from Mithril aggregator".to_string(),
+    })
 }
 
 /// Start a Mithril bootstrap download.
 /// Fetches the latest snapshot digest, then spawns mithril-client to download it.
+/// Returns the digest and the aggregator URL that served it, so the caller
+/// can later confirm (via `wait_and_finalize_bootstrap`) that this exact
+/// snapshot finished verifying, retrying against the same aggregator.
 pub async fn start_mithril_bootstrap(
     manager: &NodeManager,
     app_config: &AppConfig,
     app_data_dir: &PathBuf,
+) -> Result<(String, String), String> {
+    let (digest, aggregator_url) = fetch_latest_digest(app_config).await?;
+    start_mithril_bootstrap_for_digest(manager, app_config, app_data_dir, digest.clone(), &aggregator_url)
+        .await?;
+    Ok((digest, aggregator_url))
+}
+
+/// Base and cap for the retry backoff in `wait_and_finalize_bootstrap`:
+/// 2s, 4s, 8s, 16s, 32s, capped at 60s.
+const RETRY_BASE_DELAY_SECS: u64 = 2;
+const RETRY_MAX_DELAY_SECS: u64 = 60;
+/// Give up resuming after this many attempts rather than retrying forever
+/// against a genuinely broken aggregator or network.
+const MAX_BOOTSTRAP_ATTEMPTS: u32 = 6;
+
+/// Poll a single mithril-client attempt until it exits, persisting every
+/// observed progress line to the `mithril-progress.json` sidecar along the
+/// way. Returns `Ok(())` on a clean exit, `Err(message)` otherwise — the
+/// caller decides whether that's worth retrying.
+async fn wait_for_attempt(manager: &NodeManager, node_db_dir: &Path) -> Result<(), String> {
+    loop {
+        if let Some(line) = manager.get_logs("mithril-client", 1).await.into_iter().last() {
+            if let Some(progress) = parse_mithril_output(&line) {
+                persist_progress(node_db_dir, &progress);
+            }
+        }
+
+        match manager.get_status("mithril-client").await.map(|s| s.status) {
+            Some(ProcessStatus::Stopped) => return Ok(()),
+            Some(ProcessStatus::Error { message }) => return Err(message),
+            None => return Err("mithril-client process disappeared mid-bootstrap".to_string()),
+            _ => tokio::time::sleep(tokio::time::Duration::from_secs(2)).await,
+        }
+    }
+}
+
+/// Wait for a mithril-client bootstrap to finish, transparently retrying
+/// with exponential backoff if it exits before reaching `Complete`, then
+/// persist the verified digest marker on eventual success.
+///
+/// `mithril-client cardano-db download` already verifies the certificate
+/// chain for the snapshot up to the configured genesis verification key,
+/// and verifies the downloaded archive's content digest against the
+/// certified message before extracting — that's the trust chain IOG
+/// designed the `--genesis-verification-key` flag to provide. What it
+/// doesn't give us is a record that *this app* actually saw it succeed:
+/// on success this function writes the verified digest to a marker file
+/// in `node-db/`, which `has_chain_data` then requires to be present
+/// before considering the chain data usable.
+///
+/// Retries re-invoke mithril-client against the same `--download-dir`
+/// (`node-db/`), so whatever mithril-client already wrote there is reused
+/// rather than re-downloaded from scratch — mithril-client itself decides
+/// what, if anything, it can resume from a partial download directory.
+pub async fn wait_and_finalize_bootstrap(
+    manager: &NodeManager,
+    app_config: &AppConfig,
+    app_data_dir: &PathBuf,
+    digest: &str,
+    aggregator_url: &str,
+) -> Result<(), String> {
+    let node_db_dir = app_config.node_db_dir(app_data_dir);
+    let mut last_error = String::new();
+
+    for attempt in 0..MAX_BOOTSTRAP_ATTEMPTS {
+        match wait_for_attempt(manager, &node_db_dir).await {
+            Ok(()) => {
+                let db_dir = node_db_dir.join("db");
+                let content_digest = hash_immutable_files_streaming(&db_dir)
+                    .map_err(|e| format!("Failed to verify extracted snapshot: {e}"))?;
+                std::fs::write(content_digest_marker_path(&node_db_dir), &content_digest)
+                    .map_err(|e| format!("Failed to persist content digest: {e}"))?;
+                std::fs::write(digest_marker_path(&node_db_dir), digest)
+                    .map_err(|e| format!("Failed to persist verified snapshot digest: {e}"))?;
+
+                persist_progress(
+                    &node_db_dir,
+                    &MithrilProgress {
+                        stage: MithrilStage::Complete,
+                        progress_percent: 100.0,
+                        bytes_downloaded: 0,
+                        total_bytes: 0,
+                        message: "Bootstrap complete".to_string(),
+                    },
+                );
+                return Ok(());
+            }
+            Err(e) => {
+                last_error = e;
+                if attempt + 1 >= MAX_BOOTSTRAP_ATTEMPTS {
+                    break;
+                }
+
+                let delay_secs =
+                    (RETRY_BASE_DELAY_SECS * 2u64.pow(attempt)).min(RETRY_MAX_DELAY_SECS);
+                persist_progress(
+                    &node_db_dir,
+                    &MithrilProgress {
+                        stage: MithrilStage::Retrying,
+                        progress_percent: 0.0,
+                        bytes_downloaded: 0,
+                        total_bytes: 0,
+                        message: format!(
+                            "Attempt {} failed ({last_error}); retrying in {delay_secs}s",
+                            attempt + 1
+                        ),
+                    },
+                );
+                tokio::time::sleep(tokio::time::Duration::from_secs(delay_secs)).await;
+
+                start_mithril_bootstrap_for_digest(
+                    manager,
+                    app_config,
+                    app_data_dir,
+                    digest.to_string(),
+                    aggregator_url,
+                )
+                .await?;
+            }
+        }
+    }
+
+    persist_progress(
+        &node_db_dir,
+        &MithrilProgress {
+            stage: MithrilStage::Error,
+            progress_percent: 0.0,
+            bytes_downloaded: 0,
+            total_bytes: 0,
+            message: last_error.clone(),
+        },
+    );
+    Err(format!(
+        "Mithril bootstrap failed after {MAX_BOOTSTRAP_ATTEMPTS} attempts: {last_error}"
+    ))
+}
+
+/// Run a full Mithril bootstrap and block until it's downloaded, verified,
+/// extracted, and its digest persisted. Convenience wrapper over
+/// `start_mithril_bootstrap` + `wait_and_finalize_bootstrap` for callers
+/// (the headless CLI, tests) that want one blocking call.
+pub async fn bootstrap_and_verify(
+    manager: &NodeManager,
+    app_config: &AppConfig,
+    app_data_dir: &PathBuf,
+) -> Result<(), String> {
+    let (digest, aggregator_url) = start_mithril_bootstrap(manager, app_config, app_data_dir).await?;
+    wait_and_finalize_bootstrap(manager, app_config, app_data_dir, &digest, &aggregator_url).await
+}
+
+/// Shared implementation: spawn mithril-client for a specific digest against
+/// a specific aggregator. `aggregator_url` is the one `fetch_latest_digest`
+/// already confirmed served a verifiable snapshot for `digest` — reused
+/// as-is (rather than re-shuffling) so a retry keeps talking to the
+/// aggregator that's already proven to have this exact snapshot.
+async fn start_mithril_bootstrap_for_digest(
+    manager: &NodeManager,
+    app_config: &AppConfig,
+    app_data_dir: &PathBuf,
+    digest: String,
+    aggregator_url: &str,
 ) -> Result<(), String> {
     let db_dir = app_config.node_db_dir(app_data_dir);
     std::fs::create_dir_all(&db_dir)
         .map_err(|e| format!("Failed to create node db dir: {e}"))?;
 
-    let digest = fetch_latest_digest(app_config.mithril_aggregator_url()).await?;
-
     let args = vec![
         "cardano-db".to_string(),
         "download".to_string(),
@@ -64,9 +538,9 @@ pub async fn start_mithril_bootstrap(
         "--backend".to_string(),
         "v1".to_string(),
         "--aggregator-endpoint".to_string(),
-        app_config.mithril_aggregator_url().to_string(),
+        aggregator_url.to_string(),
         "--genesis-verification-key".to_string(),
-        app_config.mithril_genesis_vkey().to_string(),
+        app_config.mithril_genesis_vkey(),
         "--download-dir".to_string(),
         db_dir.to_string_lossy().into(),
         "--json".to_string(),
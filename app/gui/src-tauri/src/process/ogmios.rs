@@ -1,5 +1,6 @@
 use crate::config::AppConfig;
-use crate::process::manager::NodeManager;
+use crate::process::manager::{NodeManager, ProbeResult, RestartPolicy};
+use crate::process::ogmios_client::OgmiosClient;
 use std::path::Path;
 
 /// Build Ogmios command-line arguments
@@ -24,53 +25,62 @@ pub async fn start_ogmios(
     manager: &NodeManager,
     app_config: &AppConfig,
     app_data_dir: &Path,
+    ogmios_client: &OgmiosClient,
 ) -> Result<(), String> {
+    manager
+        .set_restart_policy(
+            "ogmios",
+            RestartPolicy {
+                max_retries: app_config.max_restarts,
+                initial_delay_ms: app_config.restart_backoff_ms,
+                ..RestartPolicy::default()
+            },
+        )
+        .await;
+
+    manager
+        .set_probe("ogmios", {
+            let client = ogmios_client.clone();
+            move || {
+                let client = client.clone();
+                async move {
+                    if !is_ready(&client).await {
+                        return ProbeResult::Unhealthy("Ogmios not answering".to_string());
+                    }
+                    let progress = get_sync_progress(&client);
+                    if progress >= 0.999 {
+                        ProbeResult::Ready
+                    } else {
+                        ProbeResult::Syncing(progress)
+                    }
+                }
+            }
+        })
+        .await;
+
     let args = build_ogmios_args(app_config, app_data_dir);
     manager.start("ogmios", "ogmios", args).await
 }
 
-/// Health check: GET http://127.0.0.1:{port}/health
-/// Returns true if Ogmios responds with a 200 status.
-pub async fn health_check(port: u16) -> bool {
-    let url = format!("http://127.0.0.1:{}/health", port);
-    match reqwest::get(&url).await {
-        Ok(resp) => resp.status().is_success(),
-        Err(_) => false,
-    }
+/// Readiness check for the startup wait-gate: a successful state query
+/// over the persistent connection proves Ogmios is actually answering,
+/// the same role `GET /health` used to play — but without a one-off HTTP
+/// request, since `client` is already maintaining (or retrying) a
+/// WebSocket connection in the background.
+pub async fn is_ready(client: &OgmiosClient) -> bool {
+    client.query_tip().await.is_ok()
 }
 
-/// Query chain sync progress from the Ogmios health endpoint.
-/// Returns the networkSynchronization value (0.0 to 1.0).
-/// The /health response includes:
-/// { "networkSynchronization": 0.9999, "currentEra": "Conway", ... }
-pub async fn get_sync_progress(port: u16) -> Result<f64, String> {
-    let url = format!("http://127.0.0.1:{}/health", port);
-    let resp = reqwest::get(&url)
-        .await
-        .map_err(|e| format!("Ogmios health request failed: {e}"))?;
-    let json: serde_json::Value = resp
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse Ogmios health response: {e}"))?;
-    json["networkSynchronization"]
-        .as_f64()
-        .ok_or_else(|| "Missing networkSynchronization in Ogmios health response".to_string())
+/// Chain sync progress (0.0 to 1.0), read from the chain-sync
+/// mini-protocol's cached tip data rather than polling `GET /health`.
+/// `0.0` until the first chain-sync response has arrived.
+pub fn get_sync_progress(client: &OgmiosClient) -> f64 {
+    client.cached_tip().network_synchronization
 }
 
-/// Get chain tip info from the Ogmios health endpoint.
-/// Returns (slot, block_height) if available.
-pub async fn get_tip_info(port: u16) -> Result<(u64, u64), String> {
-    let url = format!("http://127.0.0.1:{}/health", port);
-    let resp = reqwest::get(&url)
-        .await
-        .map_err(|e| format!("Ogmios health request failed: {e}"))?;
-    let json: serde_json::Value = resp
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse Ogmios health response: {e}"))?;
-
-    let tip = &json["lastKnownTip"];
-    let slot = tip["slot"].as_u64().unwrap_or(0);
-    let height = tip["height"].as_u64().unwrap_or(0);
-    Ok((slot, height))
+/// Chain tip (slot, block height), read from the same cached data as
+/// `get_sync_progress`. `(0, 0)` until the first chain-sync response.
+pub fn get_tip_info(client: &OgmiosClient) -> (u64, u64) {
+    let tip = client.cached_tip();
+    (tip.slot, tip.height)
 }
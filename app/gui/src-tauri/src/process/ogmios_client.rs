@@ -0,0 +1,302 @@
+//! Persistent WebSocket client for Ogmios's JSON-RPC interface.
+//!
+//! Replaces polling `GET /health` with a single long-lived connection that
+//! speaks the Ouroboros local-state-query mini-protocol (for one-off
+//! queries like protocol parameters or a UTxO set) and the chain-sync
+//! mini-protocol (for a continuous stream of the node's replay progress
+//! against the network tip). A background task owns the socket; commands
+//! sent through `OgmiosClient` are multiplexed onto it by request id and
+//! resolved via oneshot channels, while chain-sync pushes update a
+//! `watch` cell that `get_sync_progress`/`get_tip_info` read from directly
+//! — no network round-trip on the hot status-polling path.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, watch};
+
+use futures_util::{SinkExt, StreamExt};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Chain tip / replay-progress data kept up to date by the chain-sync
+/// mini-protocol. Cheap to clone and read from the status-polling path.
+#[derive(Clone, Debug, Default)]
+pub struct CachedTip {
+    /// Slot of the most recently replayed local block.
+    pub slot: u64,
+    /// Block height of the most recently replayed local block.
+    pub height: u64,
+    /// Era of the most recently replayed local block, e.g. "Conway".
+    pub era: String,
+    /// Slot of the network's current tip, as reported alongside each
+    /// chain-sync response.
+    pub network_tip_slot: u64,
+    /// `slot / network_tip_slot`, clamped to `[0.0, 1.0]`. `0.0` until the
+    /// first chain-sync response arrives.
+    pub network_synchronization: f64,
+}
+
+struct PendingRequest {
+    method: String,
+    params: Value,
+    reply: oneshot::Sender<Result<Value, String>>,
+}
+
+/// Handle to a persistent Ogmios connection. Cheap to clone — clones share
+/// the same background task and cached tip.
+#[derive(Clone)]
+pub struct OgmiosClient {
+    commands: mpsc::Sender<PendingRequest>,
+    tip: watch::Receiver<CachedTip>,
+}
+
+impl OgmiosClient {
+    /// Spawn the background connection task and return a handle to it.
+    /// Returns immediately — the task connects (and reconnects, with
+    /// exponential backoff) in the background, so this is safe to call
+    /// before Ogmios has even started.
+    pub fn connect(port: u16) -> Self {
+        let (cmd_tx, cmd_rx) = mpsc::channel(32);
+        let (tip_tx, tip_rx) = watch::channel(CachedTip::default());
+
+        tauri::async_runtime::spawn(connection_loop(port, cmd_rx, tip_tx));
+
+        Self {
+            commands: cmd_tx,
+            tip: tip_rx,
+        }
+    }
+
+    /// Latest tip/sync data pushed by the chain-sync mini-protocol.
+    /// Default (`slot: 0, network_synchronization: 0.0`) until the first
+    /// response arrives after connecting.
+    pub fn cached_tip(&self) -> CachedTip {
+        self.tip.borrow().clone()
+    }
+
+    async fn request(&self, method: &str, params: Value) -> Result<Value, String> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.commands
+            .send(PendingRequest {
+                method: method.to_string(),
+                params,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| "Ogmios connection task is not running".to_string())?;
+        reply_rx
+            .await
+            .map_err(|_| "Ogmios connection dropped before replying".to_string())?
+    }
+
+    /// `queryNetwork/tip`: the upstream network's current chain tip.
+    pub async fn query_tip(&self) -> Result<Value, String> {
+        self.request("queryNetwork/tip", json!({})).await
+    }
+
+    /// `queryLedgerState/protocolParameters`: the current protocol parameters.
+    pub async fn query_protocol_parameters(&self) -> Result<Value, String> {
+        self.request("queryLedgerState/protocolParameters", json!({}))
+            .await
+    }
+
+    /// `queryLedgerState/utxo`: the UTxO set for a given address.
+    pub async fn query_utxos_by_address(&self, address: &str) -> Result<Value, String> {
+        self.request(
+            "queryLedgerState/utxo",
+            json!({ "addresses": [address] }),
+        )
+        .await
+    }
+}
+
+/// Id used for the self-perpetuating chain-sync request stream, kept
+/// distinct from the numeric ids `request()` hands out so responses can be
+/// routed to the right place without ambiguity.
+const CHAIN_SYNC_ID: &str = "chain-sync";
+
+/// Owns the WebSocket connection: reconnects with exponential backoff on
+/// any disconnect, and for each connection drives both client-requested
+/// queries and the background chain-sync stream until the socket drops.
+async fn connection_loop(
+    port: u16,
+    mut commands: mpsc::Receiver<PendingRequest>,
+    tip_tx: watch::Sender<CachedTip>,
+) {
+    const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+    const MAX_BACKOFF: Duration = Duration::from_secs(30);
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+        let url = format!("ws://127.0.0.1:{port}");
+        match tokio_tungstenite::connect_async(&url).await {
+            Ok((ws_stream, _)) => {
+                backoff = INITIAL_BACKOFF;
+                if let Err(e) = run_connection(ws_stream, &mut commands, &tip_tx).await {
+                    eprintln!("[ogmios] connection lost: {e}");
+                }
+            }
+            Err(e) => {
+                eprintln!("[ogmios] connect to {url} failed: {e}");
+            }
+        }
+
+        // All `OgmiosClient` handles were dropped — nothing left to serve.
+        if commands.is_closed() {
+            return;
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Drive one connection until it closes or errors: forward client requests
+/// onto the socket, keep the chain-sync stream advancing, and route
+/// incoming messages back to whichever side is waiting on them.
+async fn run_connection(
+    ws_stream: tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >,
+    commands: &mut mpsc::Receiver<PendingRequest>,
+    tip_tx: &watch::Sender<CachedTip>,
+) -> Result<(), String> {
+    let (mut write, mut read) = ws_stream.split();
+    let mut pending: HashMap<String, oneshot::Sender<Result<Value, String>>> = HashMap::new();
+    let next_id = AtomicU64::new(1);
+
+    // Kick off the chain-sync mini-protocol from genesis; `handle_message`
+    // re-issues the next `nextBlock` each time a response comes back, so
+    // from here on it keeps itself advancing for the life of the connection.
+    send(
+        &mut write,
+        CHAIN_SYNC_ID,
+        "findIntersection",
+        json!({ "points": ["origin"] }),
+    )
+    .await?;
+
+    loop {
+        tokio::select! {
+            cmd = commands.recv() => {
+                match cmd {
+                    Some(PendingRequest { method, params, reply }) => {
+                        let id = next_id.fetch_add(1, Ordering::SeqCst).to_string();
+                        if send(&mut write, &id, &method, params).await.is_err() {
+                            let _ = reply.send(Err("Failed to send request to Ogmios".to_string()));
+                            return Err("Failed to write to Ogmios socket".to_string());
+                        }
+                        pending.insert(id, reply);
+                    }
+                    // All client handles dropped — close this connection down.
+                    None => return Ok(()),
+                }
+            }
+            msg = read.next() => {
+                match msg {
+                    Some(Ok(Message::Text(text))) => {
+                        handle_message(&text, &mut write, &mut pending, tip_tx).await;
+                    }
+                    Some(Ok(Message::Close(_))) | None => {
+                        return Err("Ogmios closed the connection".to_string());
+                    }
+                    Some(Ok(_)) => {} // ignore ping/pong/binary frames
+                    Some(Err(e)) => return Err(format!("WebSocket error: {e}")),
+                }
+            }
+        }
+    }
+}
+
+async fn send<S>(
+    write: &mut futures_util::stream::SplitSink<S, Message>,
+    id: &str,
+    method: &str,
+    params: Value,
+) -> Result<(), ()>
+where
+    S: futures_util::Sink<Message> + Unpin,
+{
+    let payload = json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": params,
+        "id": id,
+    });
+    write.send(Message::Text(payload.to_string())).await.map_err(|_| ())
+}
+
+/// Route one incoming JSON-RPC message: either resolve a pending
+/// client request, or — if it's a chain-sync response — fold it into the
+/// cached tip and immediately request the next block.
+async fn handle_message<S>(
+    text: &str,
+    write: &mut futures_util::stream::SplitSink<S, Message>,
+    pending: &mut HashMap<String, oneshot::Sender<Result<Value, String>>>,
+    tip_tx: &watch::Sender<CachedTip>,
+) where
+    S: futures_util::Sink<Message> + Unpin,
+{
+    let Ok(value) = serde_json::from_str::<Value>(text) else {
+        return;
+    };
+    let Some(id) = value.get("id").and_then(|v| v.as_str()) else {
+        return;
+    };
+
+    if id == CHAIN_SYNC_ID {
+        update_cached_tip(&value, tip_tx);
+        // Keep the stream advancing — findIntersection's reply and every
+        // nextBlock reply both lead into another nextBlock request.
+        let _ = send(write, CHAIN_SYNC_ID, "nextBlock", json!({})).await;
+        return;
+    }
+
+    if let Some(reply) = pending.remove(id) {
+        let result = if let Some(err) = value.get("error") {
+            Err(format!("Ogmios error: {err}"))
+        } else {
+            Ok(value.get("result").cloned().unwrap_or(Value::Null))
+        };
+        let _ = reply.send(result);
+    }
+}
+
+/// Extract block/tip slot+height from a chain-sync `nextBlock` response and
+/// fold them into the cached tip. `result` looks roughly like
+/// `{"direction": "forward", "block": {"slot":.., "height":.., "era":..}, "tip": {"slot":.., "height":..}}`;
+/// anything else (e.g. `findIntersection`'s reply, which has no `block`) is
+/// a no-op here.
+fn update_cached_tip(value: &Value, tip_tx: &watch::Sender<CachedTip>) {
+    let Some(result) = value.get("result") else {
+        return;
+    };
+    let Some(block) = result.get("block") else {
+        return;
+    };
+    let Some(tip) = result.get("tip") else {
+        return;
+    };
+
+    let slot = block.get("slot").and_then(|v| v.as_u64()).unwrap_or(0);
+    let height = block.get("height").and_then(|v| v.as_u64()).unwrap_or(0);
+    let era = block
+        .get("era")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let network_tip_slot = tip.get("slot").and_then(|v| v.as_u64()).unwrap_or(0);
+
+    tip_tx.send_modify(|cached| {
+        cached.slot = slot;
+        cached.height = height;
+        cached.era = era;
+        cached.network_tip_slot = network_tip_slot;
+        cached.network_synchronization = if network_tip_slot > 0 {
+            (slot as f64 / network_tip_slot as f64).min(1.0)
+        } else {
+            0.0
+        };
+    });
+}
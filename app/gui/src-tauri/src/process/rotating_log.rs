@@ -0,0 +1,191 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Size at which a process's log file rotates to an archive.
+pub const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+/// Number of rotated archives kept (`<name>.1.log` .. `<name>.<N>.log`),
+/// oldest dropped once this is exceeded.
+pub const DEFAULT_MAX_ARCHIVES: usize = 5;
+
+/// Appends a process's captured stdout/stderr to `<dir>/<name>.log`,
+/// rotating to `<name>.1.log` .. `<name>.<max_archives>.log` once the
+/// current file passes `max_bytes`. Survives across process restarts —
+/// opened once per `NodeManager::start`/`start_command` call in append
+/// mode, so a restart continues the same file rather than truncating it.
+pub struct RotatingLogWriter {
+    dir: PathBuf,
+    name: String,
+    file: File,
+    current_size: u64,
+    max_bytes: u64,
+    max_archives: usize,
+}
+
+impl RotatingLogWriter {
+    pub fn open(dir: &Path, name: &str) -> Result<Self, String> {
+        Self::open_with_limits(dir, name, DEFAULT_MAX_BYTES, DEFAULT_MAX_ARCHIVES)
+    }
+
+    pub fn open_with_limits(
+        dir: &Path,
+        name: &str,
+        max_bytes: u64,
+        max_archives: usize,
+    ) -> Result<Self, String> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create log directory {}: {e}", dir.display()))?;
+
+        let path = dir.join(format!("{name}.log"));
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to open log file {}: {e}", path.display()))?;
+        let current_size = file.metadata().map(|m| m.len()).unwrap_or(0);
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            name: name.to_string(),
+            file,
+            current_size,
+            max_bytes,
+            max_archives,
+        })
+    }
+
+    /// Append one log line, rotating first if this write would push the
+    /// current file over `max_bytes`.
+    pub fn write_line(&mut self, line: &str) -> Result<(), String> {
+        let entry = format!("{line}\n");
+        if self.current_size + entry.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file
+            .write_all(entry.as_bytes())
+            .map_err(|e| format!("Failed to write log line: {e}"))?;
+        self.current_size += entry.len() as u64;
+        Ok(())
+    }
+
+    fn archive_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("{}.{}.log", self.name, index))
+    }
+
+    fn base_path(&self) -> PathBuf {
+        self.dir.join(format!("{}.log", self.name))
+    }
+
+    /// Fixed-window roll: drop the oldest archive, shift `i -> i+1` for the
+    /// rest, then move the current file to `.1.log` and reopen it empty.
+    fn rotate(&mut self) -> Result<(), String> {
+        let _ = std::fs::remove_file(self.archive_path(self.max_archives));
+
+        for i in (1..self.max_archives).rev() {
+            let src = self.archive_path(i);
+            if src.exists() {
+                let _ = std::fs::rename(&src, self.archive_path(i + 1));
+            }
+        }
+
+        let base_path = self.base_path();
+        std::fs::rename(&base_path, self.archive_path(1))
+            .map_err(|e| format!("Failed to rotate log file: {e}"))?;
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&base_path)
+            .map_err(|e| format!("Failed to reopen log file after rotation: {e}"))?;
+        self.current_size = 0;
+        Ok(())
+    }
+}
+
+/// Read back log lines for `name` under `dir`. `from_archive` selects
+/// `<name>.<n>.log` (1 = most recently rotated); `None` reads the live
+/// `<name>.log`. `max_lines` caps how many trailing lines are returned,
+/// defaulting to the whole file.
+pub fn read_logs(
+    dir: &Path,
+    name: &str,
+    from_archive: Option<usize>,
+    max_lines: Option<usize>,
+) -> Result<Vec<String>, String> {
+    let path = match from_archive {
+        Some(n) => dir.join(format!("{name}.{n}.log")),
+        None => dir.join(format!("{name}.log")),
+    };
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read log file {}: {e}", path.display()))?;
+    let all_lines: Vec<String> = contents.lines().map(String::from).collect();
+
+    let limit = max_lines.unwrap_or(all_lines.len());
+    let start = all_lines.len().saturating_sub(limit);
+    Ok(all_lines[start..].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_and_read_roundtrip() {
+        let dir = std::env::temp_dir().join(format!(
+            "peace-protocol-log-test-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut writer = RotatingLogWriter::open(&dir, "test-proc").unwrap();
+        writer.write_line("line one").unwrap();
+        writer.write_line("line two").unwrap();
+
+        let lines = read_logs(&dir, "test-proc", None, None).unwrap();
+        assert_eq!(lines, vec!["line one", "line two"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rotates_once_size_limit_exceeded() {
+        let dir = std::env::temp_dir().join(format!(
+            "peace-protocol-log-rotate-test-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        // Tiny limit so two lines force a rotation.
+        let mut writer = RotatingLogWriter::open_with_limits(&dir, "test-proc", 10, 5).unwrap();
+        writer.write_line("first line here").unwrap();
+        writer.write_line("second line here").unwrap();
+
+        let archive = read_logs(&dir, "test-proc", Some(1), None).unwrap();
+        assert_eq!(archive, vec!["first line here"]);
+
+        let current = read_logs(&dir, "test-proc", None, None).unwrap();
+        assert_eq!(current, vec!["second line here"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn max_lines_caps_trailing_output() {
+        let dir = std::env::temp_dir().join(format!(
+            "peace-protocol-log-maxlines-test-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut writer = RotatingLogWriter::open(&dir, "test-proc").unwrap();
+        for i in 0..5 {
+            writer.write_line(&format!("line {i}")).unwrap();
+        }
+
+        let lines = read_logs(&dir, "test-proc", None, Some(2)).unwrap();
+        assert_eq!(lines, vec!["line 3", "line 4"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}